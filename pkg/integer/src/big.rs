@@ -0,0 +1,269 @@
+use crate::{Digits, Integer};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{ToPrimitive, Zero};
+
+//  Extracting digits from an arbitrary precision integer one at a time costs a full length
+//  division per digit. Instead, the integer is split into chunks using the largest power of the
+//  radix which fits in a u64, so that each full length division yields many digits, and the
+//  digits within each chunk are extracted using machine arithmetic.
+//
+
+impl Integer for BigUint {
+    #[inline]
+    fn into_digits(self, radix: Self) -> Digits<Self> {
+        self.to_digits(radix)
+    }
+
+    fn to_digits(&self, radix: Self) -> Digits<Self> {
+        let digits = digits(self, small_radix(&radix));
+        Digits::buffered(digits.into_iter().map(BigUint::from).collect())
+    }
+
+    fn from_digits<T: Iterator<Item = Self>>(digits: T, radix: Self) -> Self {
+        let radix = small_radix(&radix);
+        let mut digits = digits.map(|d| small_digit(&d, radix)).collect::<Vec<_>>();
+        digits.reverse();
+        from_rev_digits(digits, radix)
+    }
+
+    fn from_rev_digits<T: Iterator<Item = Self>>(digits: T, radix: Self) -> Self {
+        let radix = small_radix(&radix);
+        from_rev_digits(digits.map(|d| small_digit(&d, radix)), radix)
+    }
+
+    fn num_digits(&self, radix: Self) -> usize {
+        num_digits(self, small_radix(&radix))
+    }
+
+    fn digit_sum(&self, radix: Self) -> Self {
+        BigUint::from(digit_sum(self, small_radix(&radix)))
+    }
+}
+
+/// Digits of a `BigInt` are the digits of its magnitude, the sign is ignored.
+impl Integer for BigInt {
+    #[inline]
+    fn into_digits(self, radix: Self) -> Digits<Self> {
+        self.to_digits(radix)
+    }
+
+    fn to_digits(&self, radix: Self) -> Digits<Self> {
+        let digits = digits(self.magnitude(), small_radix(radix.magnitude()));
+        Digits::buffered(digits.into_iter().map(BigInt::from).collect())
+    }
+
+    fn from_digits<T: Iterator<Item = Self>>(digits: T, radix: Self) -> Self {
+        let radix = small_radix(radix.magnitude());
+        let mut digits = digits
+            .map(|d| small_digit(d.magnitude(), radix))
+            .collect::<Vec<_>>();
+        digits.reverse();
+        BigInt::from(from_rev_digits(digits, radix))
+    }
+
+    fn from_rev_digits<T: Iterator<Item = Self>>(digits: T, radix: Self) -> Self {
+        let radix = small_radix(radix.magnitude());
+        let digits = digits.map(|d| small_digit(d.magnitude(), radix));
+        BigInt::from(from_rev_digits(digits, radix))
+    }
+
+    fn num_digits(&self, radix: Self) -> usize {
+        num_digits(self.magnitude(), small_radix(radix.magnitude()))
+    }
+
+    fn digit_sum(&self, radix: Self) -> Self {
+        BigInt::from(digit_sum(self.magnitude(), small_radix(radix.magnitude())))
+    }
+}
+
+/// Get the largest power of the radix which fits in a u64, and its exponent.
+fn chunk_radix(radix: u64) -> (u64, u32) {
+    let mut chunk = radix;
+    let mut width = 1;
+    while let Some(next) = chunk.checked_mul(radix) {
+        chunk = next;
+        width += 1;
+    }
+    (chunk, width)
+}
+
+/// Split the number into chunks of digits, starting with the least significant chunk.
+fn chunks(n: &BigUint, chunk: u64) -> Vec<u64> {
+    let chunk = BigUint::from(chunk);
+    let mut n = n.clone();
+    let mut chunks = Vec::new();
+    while !n.is_zero() {
+        let (q, r) = num_integer::Integer::div_rem(&n, &chunk);
+        chunks.push(r.to_u64().unwrap());
+        n = q;
+    }
+    chunks
+}
+
+fn digits(n: &BigUint, radix: u64) -> Vec<u64> {
+    let (chunk, width) = chunk_radix(radix);
+    let mut digits = Vec::new();
+    for mut c in chunks(n, chunk) {
+        for _ in 0..width {
+            digits.push(c % radix);
+            c /= radix;
+        }
+    }
+
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+    digits
+}
+
+fn from_rev_digits<T: IntoIterator<Item = u64>>(digits: T, radix: u64) -> BigUint {
+    let (chunk, width) = chunk_radix(radix);
+    let mut result = BigUint::zero();
+    let mut acc = 0;
+    let mut len = 0;
+    for d in digits {
+        acc = acc * radix + d;
+        len += 1;
+        if len == width {
+            result = result * chunk + acc;
+            acc = 0;
+            len = 0;
+        }
+    }
+    result * BigUint::from(radix).pow(len) + acc
+}
+
+fn num_digits(n: &BigUint, radix: u64) -> usize {
+    if n.is_zero() {
+        return 1;
+    }
+
+    //  The bit length gives an estimate of the number of digits, which is corrected exactly in
+    //  case of floating point error.
+    //
+    let mut count = ((n.bits() - 1) as f64 / (radix as f64).log2()) as u32;
+    let radix = BigUint::from(radix);
+    let mut order = radix.pow(count);
+    while order > *n {
+        order /= &radix;
+        count -= 1;
+    }
+    while order <= *n {
+        order *= &radix;
+        count += 1;
+    }
+    count as usize
+}
+
+fn digit_sum(n: &BigUint, radix: u64) -> u128 {
+    let (chunk, _) = chunk_radix(radix);
+    let mut sum = 0u128;
+    for mut c in chunks(n, chunk) {
+        while c > 0 {
+            sum += (c % radix) as u128;
+            c /= radix;
+        }
+    }
+    sum
+}
+
+fn small_radix(radix: &BigUint) -> u64 {
+    radix
+        .to_u64()
+        .filter(|&r| r >= 2)
+        .expect("Radix must be at least 2 and fit in a u64")
+}
+
+/// Get the digit as a u64, checking it is less than the radix so that the chunks cannot overflow.
+fn small_digit(digit: &BigUint, radix: u64) -> u64 {
+    digit
+        .to_u64()
+        .filter(|&d| d < radix)
+        .expect("Digit must be less than the radix")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Integer;
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Num;
+
+    const NUMBER: &str = "37107287533902102798797998220837590246510135740250463769376770";
+
+    #[test]
+    fn digits() {
+        let n = BigUint::from_str_radix(NUMBER, 10).unwrap();
+        for radix in [2u32, 3, 7, 10, 16, 36] {
+            let expected = n
+                .to_str_radix(radix)
+                .chars()
+                .rev()
+                .map(|c| BigUint::from(c.to_digit(radix).unwrap()))
+                .collect::<Vec<_>>();
+            let radix = BigUint::from(radix);
+
+            assert_eq!(expected, n.to_digits(radix.clone()).collect::<Vec<_>>());
+            assert_eq!(
+                expected.iter().rev().cloned().collect::<Vec<_>>(),
+                n.to_digits(radix.clone()).rev().collect::<Vec<_>>()
+            );
+            assert_eq!(expected.len(), n.num_digits(radix.clone()));
+            assert_eq!(
+                n,
+                BigUint::from_digits(expected.iter().cloned(), radix.clone())
+            );
+            assert_eq!(
+                n,
+                BigUint::from_rev_digits(expected.into_iter().rev(), radix)
+            );
+        }
+
+        assert_eq!(
+            0,
+            BigUint::from(0u32).to_digits(BigUint::from(10u32)).count()
+        );
+        assert_eq!(1, BigUint::from(0u32).num_digits(BigUint::from(10u32)));
+        assert_eq!(1, BigUint::from(9u32).num_digits(BigUint::from(10u32)));
+        assert_eq!(2, BigUint::from(10u32).num_digits(BigUint::from(10u32)));
+    }
+
+    #[test]
+    fn digit_sum() {
+        let ten = BigUint::from(10u32);
+        assert_eq!(
+            BigUint::from(1366u32),
+            BigUint::from(2u32).pow(1000).digit_sum(ten.clone())
+        );
+        assert_eq!(
+            BigUint::from(648u32),
+            BigUint::from(100u32).factorial().digit_sum(ten)
+        );
+        assert!(!BigUint::from(2u32)
+            .pow(64)
+            .is_palindromic(BigUint::from(2u32)));
+    }
+
+    #[test]
+    fn signed() {
+        let ten = BigInt::from(10);
+        let n = -BigInt::from_str_radix(NUMBER, 10).unwrap();
+        assert_eq!(NUMBER.len(), n.num_digits(ten.clone()));
+        assert_eq!(BigInt::from(0), n.to_digits(ten.clone()).next().unwrap());
+        assert_eq!(
+            BigInt::from(3),
+            n.to_digits(ten.clone()).next_back().unwrap()
+        );
+        assert_eq!(
+            -n.clone(),
+            BigInt::from_digits(n.to_digits(ten.clone()), ten.clone())
+        );
+        assert_eq!(BigInt::from(279), n.digit_sum(ten));
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_out_of_range() {
+        let digits = [9u32, 10, 1].map(BigUint::from);
+        BigUint::from_digits(digits.into_iter(), BigUint::from(10u32));
+    }
+}
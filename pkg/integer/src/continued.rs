@@ -0,0 +1,407 @@
+use crate::{Integer, Ratio};
+use num_integer::Roots;
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, Signed};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A quadratic irrational (P + √D) / Q, which has an eventually periodic continued fraction.
+///
+/// If D is a perfect square the number is rational, and its expansion is finite. The integer type
+/// must be signed, and wide enough for D * Q<sup>2</sup>. Operations panic rather than wrap if an
+/// intermediate value overflows, so use `BigInt` for inputs near the limits of the primitive
+/// types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuadraticIrrational<T> {
+    p: T,
+    d: T,
+    q: T,
+}
+
+impl<T> QuadraticIrrational<T>
+where
+    T: Integer + Roots + Signed + CheckedAdd + CheckedSub + CheckedMul,
+{
+    /// Construct the number (p + √d) / q. Panics if `d` is negative or `q` is zero.
+    pub fn new(p: T, d: T, q: T) -> Self {
+        assert!(!d.is_negative(), "Radicand must be non-negative");
+        assert!(!q.is_zero(), "Denominator must be non-zero");
+
+        //  The expansion relies on Q dividing D - P^2 at every step. If it does not hold
+        //  initially, scaling all three by |Q| establishes it without changing the value.
+        //
+        let rem = d.checked_sub(&checked(p.checked_mul(&p)));
+        if checked(rem).is_multiple_of(&q) {
+            return QuadraticIrrational { p, d, q };
+        }
+        let scale = q.abs();
+        QuadraticIrrational {
+            p: checked(p.checked_mul(&scale)),
+            d: checked(d.checked_mul(&scale).and_then(|d| d.checked_mul(&scale))),
+            q: checked(q.checked_mul(&scale)),
+        }
+    }
+
+    /// Construct the number √n.
+    #[inline]
+    pub fn sqrt(n: T) -> Self {
+        QuadraticIrrational::new(T::zero(), n, T::one())
+    }
+
+    /// Create an iterator over the partial quotients [a0; a1, a2, ...].
+    #[inline]
+    pub fn partial_quotients(&self) -> QuadraticQuotients<T> {
+        let root = self.d.integer_sqrt();
+        QuadraticQuotients {
+            p: self.p.clone(),
+            d: self.d.clone(),
+            q: self.q.clone(),
+            square: root.clone() * root.clone() == self.d,
+            root,
+        }
+    }
+
+    /// Split the continued fraction into the terms before the period and the terms of one
+    /// period. For example √23 = [4; (1, 3, 1, 8)] gives `([4], [1, 3, 1, 8])`. The period is
+    /// empty if the number is rational.
+    pub fn periodic_expansion(&self) -> (Vec<T>, Vec<T>)
+    where
+        T: Hash,
+    {
+        //  Each partial quotient is determined by the state (P, Q), so the expansion repeats as
+        //  soon as a state recurs.
+        //
+        let mut iter = self.partial_quotients();
+        let mut seen = HashMap::new();
+        let mut terms = Vec::new();
+        loop {
+            if let Some(start) = seen.insert((iter.p.clone(), iter.q.clone()), terms.len()) {
+                let period = terms.split_off(start);
+                return (terms, period);
+            }
+            match iter.next() {
+                Some(a) => terms.push(a),
+                None => return (terms, Vec::new()),
+            }
+        }
+    }
+}
+
+/// An iterator which yields the partial quotients of a quadratic irrational.
+#[derive(Clone)]
+pub struct QuadraticQuotients<T> {
+    p: T,
+    d: T,
+    q: T,
+    root: T,
+    square: bool,
+}
+
+impl<T> Iterator for QuadraticQuotients<T>
+where
+    T: Integer + Signed + CheckedAdd + CheckedSub + CheckedMul,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.q.is_zero() {
+            return None;
+        }
+
+        //  For an irrational root s < √D < s + 1, so when Q is negative the floor of the
+        //  quotient is found from the upper bound P + s + 1 instead.
+        //
+        let mut upper = checked(self.p.checked_add(&self.root));
+        if self.q.is_negative() && !self.square {
+            upper = checked(upper.checked_add(&T::one()));
+        }
+        let a = upper.div_floor(&self.q);
+        let p = checked(
+            a.checked_mul(&self.q)
+                .and_then(|aq| aq.checked_sub(&self.p)),
+        );
+        let rem = checked(self.d.checked_sub(&checked(p.checked_mul(&p))));
+        self.q = rem / self.q.clone();
+        self.p = p;
+        Some(a)
+    }
+}
+
+/// Unwrap the result of a checked operation on the state of a quadratic irrational.
+#[inline]
+fn checked<T>(value: Option<T>) -> T {
+    value.expect("Quadratic irrational overflowed the integer type")
+}
+
+/// An iterator which yields the convergents h<sub>n</sub> / k<sub>n</sub> of a continued
+/// fraction, given its partial quotients. The convergents are exact, so big integers can be used
+/// for long expansions.
+#[derive(Clone)]
+pub struct Convergents<I, T> {
+    quotients: I,
+    prev: (T, T),
+    curr: (T, T),
+}
+
+impl<I: Iterator<Item = T>, T: Integer> Convergents<I, T> {
+    /// Create an iterator over the convergents of the partial quotients [a0; a1, a2, ...].
+    pub fn new<Q: IntoIterator<IntoIter = I>>(quotients: Q) -> Self {
+        Convergents {
+            quotients: quotients.into_iter(),
+            prev: (T::zero(), T::one()),
+            curr: (T::one(), T::zero()),
+        }
+    }
+}
+
+impl<I: Iterator<Item = T>, T: Integer> Iterator for Convergents<I, T> {
+    type Item = Ratio<T>;
+
+    fn next(&mut self) -> Option<Ratio<T>> {
+        //  h_n = a_n * h_(n-1) + h_(n-2), and similarly for k_n. Consecutive convergents satisfy
+        //  h_n * k_(n-1) - h_(n-1) * k_n = ±1, so each one is already in lowest terms.
+        //
+        let a = self.quotients.next()?;
+        let (h1, k1) = self.curr.clone();
+        let (h0, k0) = std::mem::replace(&mut self.prev, self.curr.clone());
+        self.curr = (a.clone() * h1 + h0, a * k1 + k0);
+        Some(Ratio::new_reduced(self.curr.0.clone(), self.curr.1.clone()))
+    }
+}
+
+/// Find the closest rational to the number with the partial quotients [a0; a1, a2, ...], among
+/// those with a denominator of at most `max_denom`. Ties are broken by the smaller denominator.
+/// A finite expansion must be in canonical form, with a last term greater than one.
+pub fn best_approximation<T, I>(quotients: I, max_denom: T) -> Ratio<T>
+where
+    T: Integer,
+    I: IntoIterator<Item = T>,
+{
+    assert!(max_denom >= T::one(), "Denominator bound must be positive");
+
+    //  The best approximation is either the last convergent h_n / k_n within the bound, or the
+    //  semiconvergent (m * h_n + h_(n-1)) / (m * k_n + k_(n-1)) with the largest m allowed by the
+    //  bound. The semiconvergent is closer when 2m > a_(n+1) and further when 2m < a_(n+1). In
+    //  the remaining case the number is compared against the midpoint of the two candidates.
+    //
+    let mut quotients = quotients.into_iter();
+    let mut seen = Vec::new();
+    let (mut h0, mut k0) = (T::zero(), T::one());
+    let (mut h1, mut k1) = (T::one(), T::zero());
+    for a in quotients.by_ref() {
+        seen.push(a.clone());
+        let k = a.clone() * k1.clone() + k0.clone();
+        if k <= max_denom {
+            let h = a * h1.clone() + h0;
+            (h0, k0, h1, k1) = (h1, k1, h, k);
+            continue;
+        }
+
+        let m = (max_denom - k0.clone()) / k1.clone();
+        let convergent = Ratio::new_reduced(h1.clone(), k1.clone());
+        if m.is_zero() {
+            return convergent;
+        }
+        let semi = Ratio::new_reduced(m.clone() * h1 + h0, m.clone() * k1 + k0);
+        return match (m.clone() + m).cmp(&a) {
+            Ordering::Greater => semi,
+            Ordering::Less => convergent,
+            Ordering::Equal => {
+                let half = Ratio::new(T::one(), T::one() + T::one());
+                let mid = (convergent.clone() + semi.clone()) * half;
+                let side = cmp_continued(
+                    seen.into_iter().chain(quotients),
+                    mid.to_continued_fraction().into_iter(),
+                );
+                if side == Ordering::Equal || side == convergent.cmp(&mid) {
+                    convergent
+                } else {
+                    semi
+                }
+            }
+        };
+    }
+    Ratio::new_reduced(h1, k1)
+}
+
+/// Compare two numbers by their partial quotients, consuming only as many terms as needed.
+fn cmp_continued<T, X, Y>(mut x: X, mut y: Y) -> Ordering
+where
+    T: Integer,
+    X: Iterator<Item = T>,
+    Y: Iterator<Item = T>,
+{
+    //  A larger term means a larger number at even positions and a smaller number at odd
+    //  positions. When one expansion ends first, its last complete quotient is the smaller of the
+    //  two at the previous position.
+    //
+    let mut flipped = false;
+    loop {
+        let ordering = match (x.next(), y.next()) {
+            (Some(a), Some(b)) if a == b => {
+                flipped = !flipped;
+                continue;
+            }
+            (Some(a), Some(b)) => a.cmp(&b),
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (None, None) => return Ordering::Equal,
+        };
+        return if flipped {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_approximation, Convergents, QuadraticIrrational};
+    use crate::Ratio;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn sqrt_expansion() {
+        assert_eq!(
+            (vec![4], vec![1, 3, 1, 8]),
+            QuadraticIrrational::sqrt(23i64).periodic_expansion()
+        );
+        assert_eq!(
+            (vec![1], vec![2]),
+            QuadraticIrrational::sqrt(2i64).periodic_expansion()
+        );
+        assert_eq!(
+            (vec![3], vec![]),
+            QuadraticIrrational::sqrt(9i64).periodic_expansion()
+        );
+
+        let odd = (2..=13)
+            .map(|n| {
+                QuadraticIrrational::sqrt(n as i64)
+                    .periodic_expansion()
+                    .1
+                    .len()
+            })
+            .filter(|len| len % 2 == 1)
+            .count();
+        assert_eq!(4, odd);
+    }
+
+    #[test]
+    fn quadratic_expansion() {
+        //  The golden ratio (1 + √5) / 2 = [1; (1)]
+        assert_eq!(
+            (vec![], vec![1]),
+            QuadraticIrrational::new(1i64, 5, 2).periodic_expansion()
+        );
+
+        //  (3 - √7) / 2 ≈ 0.177 = [0; 5, (1, 1, 1, 4)]
+        assert_eq!(
+            (vec![0, 5], vec![1, 1, 1, 4]),
+            QuadraticIrrational::new(-3, 7, -2).periodic_expansion()
+        );
+
+        //  -√2 = [-2; 1, 1, (2)]
+        assert_eq!(
+            vec![-2, 1, 1, 2, 2, 2],
+            QuadraticIrrational::new(0, 2, -1)
+                .partial_quotients()
+                .take(6)
+                .collect::<Vec<_>>()
+        );
+
+        //  Quotients are not truncated, even when they exceed the range of the inputs.
+        assert_eq!(
+            Some((1i128 << 63) + 1),
+            QuadraticIrrational::new(i64::MAX as i128, 4, 1)
+                .partial_quotients()
+                .next()
+        );
+
+        //  Normalising (1 + √(2^64 - 1)) / 2^40 multiplies D by 2^80.
+        let big = QuadraticIrrational::new(
+            BigInt::from(1),
+            BigInt::from(u64::MAX),
+            BigInt::from(1u64 << 40),
+        );
+        assert_eq!(
+            vec![0, 255, 1, 16_777_215]
+                .into_iter()
+                .map(BigInt::from)
+                .collect::<Vec<_>>(),
+            big.partial_quotients().take(4).collect::<Vec<_>>()
+        );
+
+        //  (1 + √9) / 8 = 1 / 2
+        assert_eq!(
+            vec![0, 2],
+            QuadraticIrrational::new(1, 9, 8)
+                .partial_quotients()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn convergents() {
+        let sqrt2 = QuadraticIrrational::sqrt(BigInt::from(2)).partial_quotients();
+        assert_eq!(
+            vec!["1", "3/2", "7/5", "17/12", "41/29"],
+            Convergents::new(sqrt2)
+                .take(5)
+                .map(|r| r.to_string())
+                .collect::<Vec<_>>()
+        );
+
+        //  e = [2; 1, 2, 1, 1, 4, 1, 1, 6, ...]
+        let e = (0..).map(|i| match i {
+            0 => 2u64,
+            i if i % 3 == 2 => 2 * (i as u64 + 1) / 3,
+            _ => 1,
+        });
+        assert_eq!(Some(Ratio::new(1457, 536)), Convergents::new(e).nth(9));
+
+        //  The fundamental solution of x^2 - 13y^2 = 1 is a convergent of √13.
+        let sqrt13 = QuadraticIrrational::sqrt(BigInt::from(13)).partial_quotients();
+        let pell = Convergents::new(sqrt13)
+            .find(|r| r.numer() * r.numer() - 13 * r.denom() * r.denom() == BigInt::from(1))
+            .unwrap();
+        assert_eq!(Ratio::new(BigInt::from(649), BigInt::from(180)), pell);
+    }
+
+    #[test]
+    fn best_rational_approximation() {
+        let pi = [3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3];
+        assert_eq!(Ratio::new(3, 1), best_approximation(pi, 1));
+        assert_eq!(Ratio::new(22, 7), best_approximation(pi, 10));
+        assert_eq!(Ratio::new(311, 99), best_approximation(pi, 100));
+        assert_eq!(Ratio::new(355, 113), best_approximation(pi, 200));
+
+        let sqrt2 = QuadraticIrrational::sqrt(2i64).partial_quotients();
+        assert_eq!(Ratio::new(7, 5), best_approximation(sqrt2, 10));
+
+        for q in 1..40i64 {
+            for p in -q..3 * q {
+                let x = Ratio::new(p, q);
+                for bound in 1..15 {
+                    let expected = (1..=bound)
+                        .map(|d| {
+                            let n = (x * Ratio::from(d)).floor();
+                            let lo = Ratio::new(n, d);
+                            let hi = Ratio::new(n + 1, d);
+                            let dist = |r: Ratio<i64>| if r < x { x - r } else { r - x };
+                            if dist(hi) < dist(lo) {
+                                (dist(hi), hi)
+                            } else {
+                                (dist(lo), lo)
+                            }
+                        })
+                        .min_by(|a, b| a.0.cmp(&b.0).then(a.1.denom().cmp(b.1.denom())))
+                        .unwrap()
+                        .1;
+                    assert_eq!(expected, x.best_approximation(bound));
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,27 @@
-use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+use num_integer::Roots;
+use num_traits::{CheckedAdd, CheckedMul, FromPrimitive, One, ToPrimitive, Zero};
+use std::ops::RangeInclusive;
+use std::vec;
+
+mod big;
+mod continued;
+mod modular;
+mod palindrome;
+mod pandigital;
+mod ratio;
+mod roots;
+mod transform;
+
+pub use continued::{
+    best_approximation, Convergents, QuadraticIrrational, QuadraticQuotients,
+};
+pub use modular::{
+    add_mod, crt, mod_inv, mod_pow, mul_mod, sub_mod, DynModInt, ModInt, Montgomery,
+};
+pub use palindrome::{double_base_palindromes, PalindromeIter};
+pub use pandigital::{is_pandigital_concat, Pandigitals};
+pub use ratio::Ratio;
+pub use transform::{LeftTruncations, RightTruncations, Rotations};
 
 /// Super trait extension of `num_integer::Integer`, which is useful for Project Euler problems.
 pub trait Integer: num_integer::Integer + Clone + FromPrimitive + ToPrimitive {
@@ -39,6 +62,165 @@ pub trait Integer: num_integer::Integer + Clone + FromPrimitive + ToPrimitive {
         result
     }
 
+    /// Returns the number of digits in the given radix. Zero is considered to have one digit.
+    fn num_digits(&self, radix: Self) -> usize {
+        self.to_digits(radix).count().max(1)
+    }
+
+    /// Returns the sum of the digits in the given radix.
+    fn digit_sum(&self, radix: Self) -> Self {
+        self.to_digits(radix).fold(Self::zero(), |acc, d| acc + d)
+    }
+
+    /// Returns the product of the digits in the given radix.
+    fn digit_product(&self, radix: Self) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+        self.to_digits(radix).fold(Self::one(), |acc, d| acc * d)
+    }
+
+    /// Returns the number of occurrences of each digit, where the radix is the array length.
+    /// Zero is considered to have a single zero digit.
+    fn digit_histogram<const RADIX: usize>(&self) -> [u32; RADIX] {
+        let mut histogram = [0; RADIX];
+        if self.is_zero() {
+            histogram[0] = 1;
+        }
+
+        let radix = Self::from_usize(RADIX).unwrap();
+        for d in self.to_digits(radix) {
+            histogram[d.to_usize().unwrap()] += 1;
+        }
+        histogram
+    }
+
+    /// Returns a key which is equal for two numbers if and only if their digits are a
+    /// permutation of each other. Supports radices up to 16, and up to 255 occurrences of each
+    /// digit.
+    fn digit_signature(&self, radix: Self) -> u128 {
+        assert!(radix.to_u32().is_some_and(|r| r <= 16), "Radix must be at most 16");
+
+        //  Each digit is assigned an 8 bit counter, so the packed counters are canonical.
+        //
+        let mut counts = [0u8; 16];
+        if self.is_zero() {
+            counts[0] = 1;
+        }
+        for d in self.to_digits(radix) {
+            let count = &mut counts[d.to_usize().unwrap()];
+            *count = count.checked_add(1).expect("Too many occurrences of a digit");
+        }
+        counts
+            .iter()
+            .rev()
+            .fold(0, |acc, &count| (acc << 8) | count as u128)
+    }
+
+    /// Returns a bitmask with a bit set for each digit, or `None` if any digit repeats. The radix
+    /// must be at most 64.
+    fn digit_mask(&self, radix: Self) -> Option<u64> {
+        if self.is_zero() {
+            return Some(1);
+        }
+
+        let mut mask = 0u64;
+        for d in self.to_digits(radix) {
+            let bit = 1 << d.to_u32().unwrap();
+            if mask & bit != 0 {
+                return None;
+            }
+            mask |= bit;
+        }
+        Some(mask)
+    }
+
+    /// Returns `true` if the number uses each digit in the range exactly once, and no other
+    /// digits, such as 1 to 9 or 0 to 9 pandigital numbers.
+    #[inline]
+    fn is_pandigital(&self, radix: Self, digits: RangeInclusive<u32>) -> bool {
+        self.digit_mask(radix) == Some(pandigital::range_mask(digits))
+    }
+
+    /// Creates an Iterator over each rotation of the digits, starting with the number itself.
+    /// Rotations which do not fit in the integer type are skipped.
+    #[inline]
+    fn rotations(&self, radix: Self) -> Rotations<Self>
+    where
+        Self: CheckedAdd + CheckedMul,
+    {
+        Rotations::new(self.clone(), radix)
+    }
+
+    /// Creates an Iterator over the number followed by each truncation formed by removing its
+    /// leading digits. There is one truncation per removed digit, so a zero which becomes the
+    /// leading digit repeats the previous truncation, e.g. 103, 3, 3.
+    #[inline]
+    fn left_truncations(&self, radix: Self) -> LeftTruncations<Self> {
+        LeftTruncations::new(self.clone(), radix)
+    }
+
+    /// Creates an Iterator over the number followed by each truncation formed by removing its
+    /// trailing digits.
+    #[inline]
+    fn right_truncations(&self, radix: Self) -> RightTruncations<Self> {
+        RightTruncations::new(self.clone(), radix)
+    }
+
+    /// Returns the number formed by reversing the digits. Trailing zeros become leading zeros,
+    /// and are dropped. Overflows like the arithmetic operators if the result does not fit in the
+    /// integer type, see `checked_reverse_digits`.
+    fn reverse_digits(&self, radix: Self) -> Self {
+        let mut n = self.clone();
+        let mut result = Self::zero();
+        while !n.is_zero() {
+            let (q, r) = n.div_rem(&radix);
+            result = result * radix.clone() + r;
+            n = q;
+        }
+        result
+    }
+
+    /// Returns the number formed by reversing the digits, or `None` if it does not fit in the
+    /// integer type.
+    fn checked_reverse_digits(&self, radix: Self) -> Option<Self>
+    where
+        Self: CheckedAdd + CheckedMul,
+    {
+        let mut n = self.clone();
+        let mut result = Self::zero();
+        while !n.is_zero() {
+            let (q, r) = n.div_rem(&radix);
+            result = result.checked_mul(&radix)?.checked_add(&r)?;
+            n = q;
+        }
+        Some(result)
+    }
+
+    /// Returns the number formed by appending the digits of `other` to the digits of the number.
+    /// Overflows like the arithmetic operators if the result does not fit in the integer type,
+    /// see `checked_concat`.
+    fn concat(&self, other: Self, radix: Self) -> Self {
+        let mut shifted = self.clone();
+        for _ in 0..other.num_digits(radix.clone()) {
+            shifted = shifted * radix.clone();
+        }
+        shifted + other
+    }
+
+    /// Returns the number formed by appending the digits of `other` to the digits of the number,
+    /// or `None` if it does not fit in the integer type.
+    fn checked_concat(&self, other: Self, radix: Self) -> Option<Self>
+    where
+        Self: CheckedAdd + CheckedMul,
+    {
+        let mut shifted = self.clone();
+        for _ in 0..other.num_digits(radix.clone()) {
+            shifted = shifted.checked_mul(&radix)?;
+        }
+        shifted.checked_add(&other)
+    }
+
     /// Returns `true` if the number is unchanged when its digits are reversed.
     fn is_palindromic(&self, radix: Self) -> bool {
         let mut digits = self.to_digits(radix);
@@ -54,6 +236,76 @@ pub trait Integer: num_integer::Integer + Clone + FromPrimitive + ToPrimitive {
         }
     }
 
+    /// Returns the number raised to the given power, modulo `m`.
+    #[inline]
+    fn mod_pow(&self, exp: u64, m: Self) -> Self {
+        modular::mod_pow(self.clone(), exp, m)
+    }
+
+    /// Returns the multiplicative inverse of the number modulo `m`, or `None` if the number is
+    /// not coprime to `m`.
+    #[inline]
+    fn mod_inv(&self, m: Self) -> Option<Self> {
+        modular::mod_inv(self.clone(), m)
+    }
+
+    /// Returns the truncated principal square root of the number.
+    #[inline]
+    fn integer_sqrt(&self) -> Self
+    where
+        Self: Roots,
+    {
+        Roots::sqrt(self)
+    }
+
+    /// Returns the truncated principal cube root of the number.
+    #[inline]
+    fn integer_cbrt(&self) -> Self
+    where
+        Self: Roots,
+    {
+        Roots::cbrt(self)
+    }
+
+    /// Returns the truncated principal `n`th root of the number.
+    #[inline]
+    fn integer_nth_root(&self, n: u32) -> Self
+    where
+        Self: Roots,
+    {
+        Roots::nth_root(self, n)
+    }
+
+    /// Returns `true` if the number is the square of an integer. Most non-squares are rejected
+    /// using quadratic residues, without computing a root.
+    #[inline]
+    fn is_perfect_square(&self) -> bool
+    where
+        Self: Roots,
+    {
+        roots::is_perfect_square(self)
+    }
+
+    /// Returns the base and exponent such that base<sup>exp</sup> equals the number, with the
+    /// largest possible exponent of at least two. Returns `None` if the number is less than two
+    /// or is not a perfect power.
+    #[inline]
+    fn is_perfect_power(&self) -> Option<(Self, u32)>
+    where
+        Self: Roots,
+    {
+        roots::is_perfect_power(self)
+    }
+
+    /// Returns the number raised to the given power, or `None` if it overflows.
+    #[inline]
+    fn checked_power(&self, exp: u32) -> Option<Self>
+    where
+        Self: CheckedMul,
+    {
+        roots::checked_power(self, exp)
+    }
+
     /// Returns the factorial (!) of the number, which is equal to the product of all
     /// positive integers less than or equal to the given positive integer.
     fn factorial(&self) -> Self {
@@ -75,17 +327,27 @@ macro_rules! integer_trait_impl {
     )*)
 }
 
-integer_trait_impl!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+integer_trait_impl!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
 
 /// An iterator which yields the digits of an integer.
 #[derive(Clone)]
 pub struct Digits<T> {
-    /// The value of the integer.
-    num: T,
-    /// The base of the integer representation.
-    radix: T,
-    /// The order of magnitude of the digit, given the radix.
-    order: T,
+    inner: DigitsInner<T>,
+}
+
+#[derive(Clone)]
+enum DigitsInner<T> {
+    /// Digits which are extracted one at a time using division.
+    Arithmetic {
+        /// The value of the integer.
+        num: T,
+        /// The base of the integer representation.
+        radix: T,
+        /// The order of magnitude of the digit, given the radix.
+        order: T,
+    },
+    /// Digits which have been extracted up front, from least to most significant.
+    Buffered(vec::IntoIter<T>),
 }
 
 impl<T: num_integer::Integer + Clone> Digits<T> {
@@ -94,15 +356,25 @@ impl<T: num_integer::Integer + Clone> Digits<T> {
         if num.is_zero() {
             order = Zero::zero();
         } else {
+            //  Compare against num / radix, so that the order never overflows near the maximum
+            //  value of the type.
+            //
             order = One::one();
-            let mut prod = order.clone() * radix.clone();
-            while prod <= num {
-                order = prod;
-                prod = order.clone() * radix.clone()
+            let bound = num.clone() / radix.clone();
+            while order <= bound {
+                order = order * radix.clone();
             }
         }
 
-        Digits { num, radix, order }
+        Digits {
+            inner: DigitsInner::Arithmetic { num, radix, order },
+        }
+    }
+
+    fn buffered(digits: Vec<T>) -> Self {
+        Digits {
+            inner: DigitsInner::Buffered(digits.into_iter()),
+        }
     }
 }
 
@@ -111,32 +383,45 @@ impl<T: num_integer::Integer + Clone> Iterator for Digits<T> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.order.is_zero() {
-            return None;
-        }
+        match &mut self.inner {
+            DigitsInner::Arithmetic { num, radix, order } => {
+                if order.is_zero() {
+                    return None;
+                }
 
-        let (d, r) = self.num.div_rem(&self.radix);
-        self.num = d;
-        self.order = self.order.clone() / self.radix.clone();
-        Some(r)
+                let (d, r) = num.div_rem(radix);
+                *num = d;
+                *order = order.clone() / radix.clone();
+                Some(r)
+            }
+            DigitsInner::Buffered(digits) => digits.next(),
+        }
     }
 }
 
 impl<T: num_integer::Integer + Clone> DoubleEndedIterator for Digits<T> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.order.is_zero() {
-            return None;
-        }
+        match &mut self.inner {
+            DigitsInner::Arithmetic { num, radix, order } => {
+                if order.is_zero() {
+                    return None;
+                }
 
-        let (d, r) = self.num.div_rem(&self.order);
-        self.num = r;
-        self.order = self.order.clone() / self.radix.clone();
-        Some(d)
+                let (d, r) = num.div_rem(order);
+                *num = r;
+                *order = order.clone() / radix.clone();
+                Some(d)
+            }
+            DigitsInner::Buffered(digits) => digits.next_back(),
+        }
     }
 }
 
 #[cfg(test)]
+//  Digit tests spell out leading zeros, such as 0001 for the reversed digits of 1000, so that the
+//  expected value reads like the digits it was built from.
+#[allow(clippy::zero_prefixed_literal)]
 mod tests {
     use super::Integer;
 
@@ -178,6 +463,38 @@ mod tests {
         assert_eq!(2112, usize::from_rev_digits(vec![2, 1, 1, 2].into_iter(), RADIX));
     }
 
+    #[test]
+    fn digit_statistics() {
+        const RADIX: u64 = 10;
+
+        assert_eq!(1, 0.num_digits(RADIX));
+        assert_eq!(4, 1234.num_digits(RADIX));
+        assert_eq!(20, u64::MAX.num_digits(RADIX));
+        assert_eq!(10, 1234.digit_sum(RADIX));
+        assert_eq!(24, 1234.digit_product(RADIX));
+        assert_eq!(0, 1204.digit_product(RADIX));
+        assert_eq!(0, 0.digit_product(RADIX));
+        assert_eq!(8, 255u8.num_digits(2));
+        assert_eq!(8, 255u8.digit_sum(2));
+
+        assert_eq!([1, 0, 0, 0, 0, 0, 0, 0, 0, 0], 0u64.digit_histogram::<10>());
+        assert_eq!([2, 1, 0, 0, 0, 0, 0, 0, 0, 1], 9001u64.digit_histogram::<10>());
+        assert_eq!([1, 3], 14u32.digit_histogram::<2>());
+    }
+
+    #[test]
+    fn digit_signature() {
+        const RADIX: u64 = 10;
+
+        assert_eq!(125874.digit_signature(RADIX), 251748.digit_signature(RADIX));
+        assert_eq!(1487.digit_signature(RADIX), 8147.digit_signature(RADIX));
+        assert_ne!(1123.digit_signature(RADIX), 1223.digit_signature(RADIX));
+        assert_ne!(10.digit_signature(RADIX), 100.digit_signature(RADIX));
+        assert_ne!(0.digit_signature(RADIX), 1.digit_signature(RADIX));
+        assert_eq!(0xabc.digit_signature(16), 0xcab.digit_signature(16));
+        assert_eq!(64 << 8, u64::MAX.digit_signature(2));
+    }
+
     #[test]
     fn is_palindromic() {
         const RADIX: usize = 10;
@@ -190,6 +507,14 @@ mod tests {
         assert!(!122.is_palindromic(RADIX));
     }
 
+    #[test]
+    fn mod_pow_and_inv() {
+        assert_eq!(24, 2u64.mod_pow(10, 1000));
+        assert_eq!(4, 7i32.mod_pow(2, 5));
+        assert_eq!(Some(5), 3u8.mod_inv(7));
+        assert_eq!(None, 6u8.mod_inv(9));
+    }
+
     #[test]
     fn factorial() {
         assert_eq!(1, 0.factorial());
@@ -0,0 +1,539 @@
+use crate::Integer;
+use num_traits::{CheckedMul, One, Zero};
+use std::{
+    fmt,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Compute `(a + b) mod m` for reduced operands, without overflowing the integer type.
+#[inline]
+pub fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    let gap = m.clone() - b.clone();
+    if a >= gap {
+        a - gap
+    } else {
+        a + b
+    }
+}
+
+/// Compute `(a - b) mod m` for reduced operands, without overflowing the integer type.
+#[inline]
+pub fn sub_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        a - b
+    } else {
+        m - (b - a)
+    }
+}
+
+/// Compute `(a * b) mod m` for reduced operands. Operands which fit in a `u64` are multiplied
+/// using 128-bit arithmetic, so the product never overflows.
+#[inline]
+pub fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    match (a.to_u64(), b.to_u64(), m.to_u64()) {
+        (Some(a), Some(b), Some(m)) => {
+            T::from_u64(((a as u128) * (b as u128) % (m as u128)) as u64).unwrap()
+        }
+        _ => (a * b).mod_floor(&m),
+    }
+}
+
+/// Compute `base^exp mod m` using binary exponentiation.
+pub fn mod_pow<T: Integer>(base: T, mut exp: u64, m: T) -> T {
+    let mut base = base.mod_floor(&m);
+    let mut result = T::one().mod_floor(&m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base.clone(), m.clone());
+        }
+        base = mul_mod(base.clone(), base, m.clone());
+        exp >>= 1;
+    }
+    result
+}
+
+/// Compute the modular inverse of `a` modulo `m` using the extended Euclidean algorithm, or
+/// `None` if `a` and `m` are not coprime.
+pub fn mod_inv<T: Integer>(a: T, m: T) -> Option<T> {
+    //  The Bézout coefficient of `a` is tracked modulo `m`, which keeps it non-negative and
+    //  allows the algorithm to run on unsigned types:
+    //      r(i+1) = r(i-1) - q * r(i)
+    //      t(i+1) = t(i-1) - q * t(i) (mod m)
+    //
+    let (mut r0, mut r1) = (m.clone(), a.mod_floor(&m));
+    let (mut t0, mut t1) = (T::zero(), T::one().mod_floor(&m));
+    while !r1.is_zero() {
+        let (q, r) = r0.div_rem(&r1);
+        let qt = mul_mod(q.mod_floor(&m), t1.clone(), m.clone());
+        (r0, r1) = (r1, r);
+        (t0, t1) = (t1.clone(), sub_mod(t0, qt, m.clone()));
+    }
+
+    if r0.is_one() {
+        Some(t0)
+    } else {
+        None
+    }
+}
+
+/// Solve a system of congruences `x ≡ r (mod m)` using the Chinese remainder theorem. The
+/// moduli need not be coprime. Returns the solution `(x, lcm)` with `0 <= x < lcm`, or `None`
+/// if the congruences are inconsistent or the combined modulus overflows the integer type.
+pub fn crt<T: Integer + CheckedMul>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut x = T::zero();
+    let mut l = T::one();
+    for (r, m) in congruences {
+        //  Combining x ≡ x0 (mod l) with x ≡ r (mod m), write x = x0 + l * t. Then
+        //      l * t ≡ r - x0 (mod m)
+        //  which is solvable iff g = gcd(l, m) divides r - x0, with
+        //      t ≡ ((r - x0) / g) * (l / g)^-1 (mod m / g)
+        //
+        let r = r.mod_floor(m);
+        let g = l.gcd(m);
+        let diff = sub_mod(r, x.clone().mod_floor(m), m.clone());
+        if !diff.is_multiple_of(&g) {
+            return None;
+        }
+
+        let mg = m.clone() / g.clone();
+        let inv = mod_inv((l.clone() / g.clone()).mod_floor(&mg), mg.clone())?;
+        let t = mul_mod((diff / g).mod_floor(&mg), inv, mg.clone());
+
+        //  Since x < l and t < m / g, the new solution x + l * t is below the new modulus, so
+        //  only the modulus itself can overflow.
+        //
+        let lcm = l.checked_mul(&mg)?;
+        x = x + l * t;
+        l = lcm;
+    }
+    Some((x, l))
+}
+
+/// An integer modulo `M`, where `M` is known at compile time.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+impl<const M: u64> ModInt<M> {
+    /// Construct a modular integer from any integer, reducing it into `[0, M)`.
+    #[inline]
+    pub fn new<T: Integer>(n: T) -> Self {
+        ModInt {
+            value: reduce(n, M),
+        }
+    }
+
+    /// Get the canonical representative of the residue, in `[0, M)`.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Get the modulus.
+    #[inline]
+    pub fn modulus(&self) -> u64 {
+        M
+    }
+
+    /// Raise the residue to the given power.
+    #[inline]
+    pub fn pow(&self, exp: u64) -> Self {
+        self.with_value(mod_pow(self.value, exp, M))
+    }
+
+    /// Compute the multiplicative inverse, or `None` if the residue is not a unit.
+    #[inline]
+    pub fn inv(&self) -> Option<Self> {
+        mod_inv(self.value, M).map(|v| self.with_value(v))
+    }
+
+    #[inline]
+    fn with_value(&self, value: u64) -> Self {
+        ModInt { value }
+    }
+
+    #[inline]
+    fn check_modulus(&self, _: &Self) {}
+}
+
+/// An integer modulo a modulus which is only known at runtime.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+impl DynModInt {
+    /// Construct a modular integer from any integer, reducing it into `[0, m)`.
+    #[inline]
+    pub fn new<T: Integer>(n: T, m: u64) -> Self {
+        assert!(m > 0, "Modulus must be positive");
+        DynModInt {
+            value: reduce(n, m),
+            modulus: m,
+        }
+    }
+
+    /// Get the canonical representative of the residue, in `[0, m)`.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Get the modulus.
+    #[inline]
+    pub fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Raise the residue to the given power.
+    #[inline]
+    pub fn pow(&self, exp: u64) -> Self {
+        self.with_value(mod_pow(self.value, exp, self.modulus))
+    }
+
+    /// Compute the multiplicative inverse, or `None` if the residue is not a unit.
+    #[inline]
+    pub fn inv(&self) -> Option<Self> {
+        mod_inv(self.value, self.modulus).map(|v| self.with_value(v))
+    }
+
+    #[inline]
+    fn with_value(&self, value: u64) -> Self {
+        DynModInt {
+            value,
+            modulus: self.modulus,
+        }
+    }
+
+    #[inline]
+    fn check_modulus(&self, other: &Self) {
+        assert_eq!(self.modulus, other.modulus, "Mismatched moduli");
+    }
+}
+
+/// Implement the arithmetic operators for a modular integer type.
+macro_rules! mod_int_ops_impl {
+    ($t:ty; $($gen:tt)*) => {
+        impl<$($gen)*> Add for $t {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                self.check_modulus(&rhs);
+                self.with_value(add_mod(self.value, rhs.value, self.modulus()))
+            }
+        }
+
+        impl<$($gen)*> Sub for $t {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                self.check_modulus(&rhs);
+                self.with_value(sub_mod(self.value, rhs.value, self.modulus()))
+            }
+        }
+
+        impl<$($gen)*> Mul for $t {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self {
+                self.check_modulus(&rhs);
+                self.with_value(mul_mod(self.value, rhs.value, self.modulus()))
+            }
+        }
+
+        impl<$($gen)*> Div for $t {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, rhs: Self) -> Self {
+                self.check_modulus(&rhs);
+                let inv = rhs.inv().expect("Divisor is not invertible");
+                self.with_value(mul_mod(self.value, inv.value, self.modulus()))
+            }
+        }
+
+        impl<$($gen)*> Neg for $t {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                self.with_value(sub_mod(0, self.value, self.modulus()))
+            }
+        }
+
+        impl<$($gen)*> AddAssign for $t {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($gen)*> SubAssign for $t {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<$($gen)*> MulAssign for $t {
+            #[inline]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<$($gen)*> DivAssign for $t {
+            #[inline]
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl<$($gen)*> fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.value)
+            }
+        }
+
+        impl<$($gen)*> fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} (mod {})", self.value, self.modulus())
+            }
+        }
+    };
+}
+
+mod_int_ops_impl!(ModInt<M>; const M: u64);
+mod_int_ops_impl!(DynModInt;);
+
+impl<const M: u64> Zero for ModInt<M> {
+    #[inline]
+    fn zero() -> Self {
+        ModInt { value: 0 }
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    #[inline]
+    fn one() -> Self {
+        ModInt { value: 1 % M }
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, n| acc + n)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, n| acc * n)
+    }
+}
+
+/// Montgomery form arithmetic for a fixed odd 64-bit modulus.
+///
+/// Residues are represented as `aR mod m` where `R = 2^64`, which allows multiplication to be
+/// reduced without any division. This is considerably faster than `mul_mod` in hot loops, such as
+/// primality tests and long modular exponentiations.
+#[derive(Clone, Copy, Debug)]
+pub struct Montgomery {
+    m: u64,
+    /// The inverse of `m` modulo `2^64`.
+    m_inv: u64,
+    /// The value `R^2 mod m`, used to convert into Montgomery form.
+    r2: u64,
+}
+
+impl Montgomery {
+    /// Construct a Montgomery context for the given odd modulus.
+    pub fn new(m: u64) -> Self {
+        assert!(m % 2 == 1, "Montgomery modulus must be odd");
+
+        //  Newton's iteration doubles the number of correct low bits at each step, and m is its
+        //  own inverse modulo 8, so five iterations give the inverse modulo 2^64.
+        //
+        let mut m_inv = m;
+        for _ in 0..5 {
+            m_inv = m_inv.wrapping_mul(2u64.wrapping_sub(m.wrapping_mul(m_inv)));
+        }
+
+        let r = ((1u128 << 64) % (m as u128)) as u64;
+        let r2 = ((r as u128) * (r as u128) % (m as u128)) as u64;
+        Montgomery { m, m_inv, r2 }
+    }
+
+    /// Get the modulus.
+    #[inline]
+    pub fn modulus(&self) -> u64 {
+        self.m
+    }
+
+    /// Convert a residue into Montgomery form.
+    #[inline]
+    pub fn to_mont(&self, a: u64) -> u64 {
+        self.reduce((a % self.m) as u128 * self.r2 as u128)
+    }
+
+    /// Convert a residue out of Montgomery form.
+    #[inline]
+    pub fn from_mont(&self, a: u64) -> u64 {
+        self.reduce(a as u128)
+    }
+
+    /// Multiply two residues in Montgomery form.
+    #[inline]
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Raise a residue in Montgomery form to the given power, the result is in Montgomery form.
+    pub fn pow(&self, mut a: u64, mut exp: u64) -> u64 {
+        let mut result = self.to_mont(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = self.mul(result, a);
+            }
+            a = self.mul(a, a);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Compute `tR^-1 mod m` for `t < m * 2^64`, using Montgomery reduction (REDC).
+    #[inline]
+    fn reduce(&self, t: u128) -> u64 {
+        //  q is chosen such that t - qm ≡ 0 (mod 2^64), so the low words cancel exactly and only
+        //  the high words need to be subtracted.
+        //
+        let q = (t as u64).wrapping_mul(self.m_inv);
+        let qm_hi = ((q as u128 * self.m as u128) >> 64) as u64;
+        let t_hi = (t >> 64) as u64;
+        if t_hi >= qm_hi {
+            t_hi - qm_hi
+        } else {
+            t_hi.wrapping_sub(qm_hi).wrapping_add(self.m)
+        }
+    }
+}
+
+/// Reduce any integer into `[0, m)`.
+#[inline]
+fn reduce<T: Integer>(n: T, m: u64) -> u64 {
+    match n.to_i128() {
+        Some(n) => n.rem_euclid(m as i128) as u64,
+        None => n.mod_floor(&T::from_u64(m).unwrap()).to_u64().unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crt, mod_inv, mod_pow, mul_mod, DynModInt, ModInt, Montgomery};
+    use num_bigint::BigUint;
+
+    const P: u64 = 1_000_000_007;
+    type Mp = ModInt<P>;
+
+    #[test]
+    fn pow_and_inv() {
+        assert_eq!(mod_pow(2u64, 10, 1000), 24);
+        assert_eq!(mod_pow(3i32, 0, 7), 1);
+        assert_eq!(mod_pow(-2i64, 3, 7), 6);
+        assert_eq!(mod_pow(2u64, 7830457, 10_000_000_000), 9_700_303_872);
+        assert_eq!(mod_pow(u64::MAX - 1, u64::MAX, u64::MAX), u64::MAX - 1);
+
+        assert_eq!(mod_inv(3u32, 7), Some(5));
+        assert_eq!(mod_inv(-3i32, 7), Some(2));
+        assert_eq!(mod_inv(4u32, 8), None);
+        assert_eq!(mod_inv(1u64, 1), Some(0));
+        let a = u64::MAX - 58;
+        let inv = mod_inv(a, u64::MAX).unwrap();
+        assert_eq!(mul_mod(a, inv, u64::MAX), 1);
+    }
+
+    #[test]
+    fn chinese_remainder() {
+        assert_eq!(crt(&[(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1i64, 4), (2, 6)]), None);
+        assert_eq!(crt::<u32>(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(-1i32, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[(0u64, u64::MAX), (1, u64::MAX - 1)]), None);
+
+        //  Arbitrary precision moduli are combined beyond the range of any primitive type.
+        let m1 = (BigUint::from(1u32) << 127u32) - 1u32;
+        let m2 = (BigUint::from(1u32) << 89u32) - 1u32;
+        let x = "6287243627305097446332815697731555428921396335189049879566122012";
+        assert_eq!(
+            crt(&[
+                (BigUint::from(12_345_678_901_234_567_890_123u128), m1.clone()),
+                (BigUint::from(987_654_321u32), m2.clone())
+            ]),
+            Some((x.parse().unwrap(), m1 * m2))
+        );
+    }
+
+    #[test]
+    fn mod_int() {
+        let a = Mp::new(P - 1);
+        let b = Mp::new(2u32);
+        assert_eq!((a + b).value(), 1);
+        assert_eq!((b - a).value(), 3);
+        assert_eq!((a * a).value(), 1);
+        assert_eq!((-b).value(), P - 2);
+        assert_eq!(Mp::new(-1i64), a);
+        assert_eq!((Mp::new(1u32) / b * b).value(), 1);
+        assert_eq!(b.pow(P - 1).value(), 1);
+
+        let factorial: Mp = (1..=20u64).map(Mp::new).product();
+        assert_eq!(factorial.value(), 146326063);
+
+        let mut c = b;
+        c *= b;
+        c += b;
+        c -= Mp::new(1u32);
+        assert_eq!(c.to_string(), "5");
+    }
+
+    #[test]
+    fn dyn_mod_int() {
+        let a = DynModInt::new(10u32, 12);
+        let b = DynModInt::new(5u32, 12);
+        assert_eq!((a + b).value(), 3);
+        assert_eq!((a * b).value(), 2);
+        assert_eq!((a / b).value(), 2);
+        assert_eq!(a.inv(), None);
+        assert_eq!(format!("{:?}", a), "10 (mod 12)");
+    }
+
+    #[test]
+    #[should_panic]
+    fn dyn_mod_int_mismatched() {
+        let _ = DynModInt::new(1u32, 12) + DynModInt::new(1u32, 13);
+    }
+
+    #[test]
+    fn montgomery() {
+        for m in [3, 1_000_000_007, (1 << 61) - 1, u64::MAX] {
+            let mont = Montgomery::new(m);
+            for (a, b) in [(0, 0), (1, 1), (2, m - 1), (m - 1, m - 1), (12345, 67890)] {
+                let prod = mont.from_mont(mont.mul(mont.to_mont(a), mont.to_mont(b)));
+                assert_eq!(prod, mul_mod(a, b, m));
+            }
+            let x = mont.to_mont(7);
+            assert_eq!(mont.from_mont(mont.pow(x, 1_000_003)), mod_pow(7, 1_000_003, m));
+        }
+    }
+}
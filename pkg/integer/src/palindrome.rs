@@ -0,0 +1,307 @@
+use crate::Integer;
+use num_traits::{CheckedAdd, CheckedMul};
+use std::ops::Range;
+
+/// An iterator which generates palindromes directly, rather than filtering every integer.
+///
+/// Each palindrome is determined by its leading half, so generating the palindromes below `n`
+/// takes O(√n) steps. The iterator is double-ended when it is bounded, and iterating an
+/// unbounded iterator in reverse panics. An unbounded iterator ends at the largest palindrome
+/// which fits in the integer type.
+#[derive(Clone)]
+pub struct PalindromeIter<T> {
+    radix: T,
+    front: Cursor<T>,
+    back: Option<Cursor<T>>,
+}
+
+/// A palindrome identified by its digit length and its leading half.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Cursor<T> {
+    len: u32,
+    half: T,
+}
+
+impl<T: Integer + CheckedAdd + CheckedMul> PalindromeIter<T> {
+    /// Create an unbounded iterator over every palindrome in ascending order, starting with zero.
+    pub fn new(radix: T) -> Self {
+        PalindromeIter {
+            front: Cursor {
+                len: 1,
+                half: T::zero(),
+            },
+            back: None,
+            radix,
+        }
+    }
+
+    /// Create an iterator over the palindromes with exactly `len` digits. Panics if the largest
+    /// of them does not fit in the integer type.
+    pub fn with_digits(len: u32, radix: T) -> Self {
+        assert!(len > 0, "Length must be positive");
+        let overflow = "Palindromes must fit in the integer type";
+        let front = Cursor {
+            len,
+            half: min_half(len, &radix).expect(overflow),
+        };
+        let back = Cursor {
+            len,
+            half: max_half(len, &radix).expect(overflow),
+        };
+        let iter = PalindromeIter {
+            radix,
+            front,
+            back: Some(back),
+        };
+        iter.build(iter.back.as_ref().unwrap()).expect(overflow);
+        iter
+    }
+
+    /// Create an iterator over the palindromes within the range.
+    pub fn range(range: Range<T>, radix: T) -> Self {
+        let mut iter = PalindromeIter::new(radix);
+        if range.start >= range.end {
+            iter.exhaust();
+            return iter;
+        }
+
+        iter.back = Some(iter.floor(&(range.end - T::one())));
+        match iter.ceil(&range.start) {
+            Some(front) => iter.front = front,
+            None => iter.exhaust(),
+        }
+        iter
+    }
+
+    /// End the iterator, by moving the back before every valid cursor.
+    fn exhaust(&mut self) {
+        self.back = Some(Cursor {
+            len: 0,
+            half: T::zero(),
+        });
+    }
+
+    /// Find the smallest palindrome greater than or equal to `n`, or `None` if it does not fit in
+    /// the integer type.
+    fn ceil(&self, n: &T) -> Option<Cursor<T>> {
+        let len = n.num_digits(self.radix.clone()) as u32;
+        let half = n.clone() / pow(&self.radix, len / 2).unwrap();
+        let cursor = Cursor { len, half };
+        match self.build(&cursor) {
+            Some(value) if value >= *n => Some(cursor),
+            Some(_) => self.succ(cursor),
+            None => None,
+        }
+    }
+
+    /// Find the largest palindrome less than or equal to `n`.
+    fn floor(&self, n: &T) -> Cursor<T> {
+        let len = n.num_digits(self.radix.clone()) as u32;
+        let half = n.clone() / pow(&self.radix, len / 2).unwrap();
+        let cursor = Cursor { len, half };
+        if self.build(&cursor).is_some_and(|value| value <= *n) {
+            cursor
+        } else {
+            self.pred(cursor)
+        }
+    }
+
+    /// Get the cursor of the next palindrome, or `None` if its leading half overflows.
+    fn succ(&self, cursor: Cursor<T>) -> Option<Cursor<T>> {
+        if Some(&cursor.half) == max_half(cursor.len, &self.radix).as_ref() {
+            let len = cursor.len + 1;
+            Some(Cursor {
+                len,
+                half: min_half(len, &self.radix)?,
+            })
+        } else {
+            Some(Cursor {
+                len: cursor.len,
+                half: cursor.half.checked_add(&T::one())?,
+            })
+        }
+    }
+
+    fn pred(&self, cursor: Cursor<T>) -> Cursor<T> {
+        if Some(&cursor.half) == min_half(cursor.len, &self.radix).as_ref() {
+            //  There are no palindromes before zero, so the length drops to zero which is
+            //  ordered before every valid cursor.
+            //
+            let len = cursor.len - 1;
+            let half = if len == 0 {
+                T::zero()
+            } else {
+                max_half(len, &self.radix).unwrap()
+            };
+            Cursor { len, half }
+        } else {
+            Cursor {
+                len: cursor.len,
+                half: cursor.half - T::one(),
+            }
+        }
+    }
+
+    /// Construct the palindrome by mirroring the leading half, or `None` if it overflows.
+    fn build(&self, cursor: &Cursor<T>) -> Option<T> {
+        let mut value = cursor.half.clone();
+        let mut mirror = if cursor.len % 2 == 1 {
+            cursor.half.clone() / self.radix.clone()
+        } else {
+            cursor.half.clone()
+        };
+        while !mirror.is_zero() {
+            let (q, r) = mirror.div_rem(&self.radix);
+            value = value.checked_mul(&self.radix)?.checked_add(&r)?;
+            mirror = q;
+        }
+        Some(value)
+    }
+}
+
+impl<T: Integer + CheckedAdd + CheckedMul> Iterator for PalindromeIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.back.as_ref().is_some_and(|back| self.front > *back) {
+            return None;
+        }
+
+        let Some(value) = self.build(&self.front) else {
+            self.exhaust();
+            return None;
+        };
+        match self.succ(self.front.clone()) {
+            Some(front) => self.front = front,
+            None => self.exhaust(),
+        }
+        Some(value)
+    }
+}
+
+impl<T: Integer + CheckedAdd + CheckedMul> DoubleEndedIterator for PalindromeIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        let back = self.back.clone().expect("Iterator must be bounded");
+        if self.front > back {
+            return None;
+        }
+
+        let value = self.build(&back).unwrap();
+        self.back = Some(self.pred(back));
+        Some(value)
+    }
+}
+
+/// Create an iterator over the integers within the range which are palindromic in both radices.
+/// Palindromes are generated in the first radix, and tested in the second.
+pub fn double_base_palindromes<T: Integer + CheckedAdd + CheckedMul>(
+    range: Range<T>,
+    radix: T,
+    other: T,
+) -> impl Iterator<Item = T> {
+    PalindromeIter::range(range, radix).filter(move |n| n.is_palindromic(other.clone()))
+}
+
+fn min_half<T: Integer + CheckedMul>(len: u32, radix: &T) -> Option<T> {
+    if len == 1 {
+        Some(T::zero())
+    } else {
+        pow(radix, len.div_ceil(2) - 1)
+    }
+}
+
+fn max_half<T: Integer + CheckedMul>(len: u32, radix: &T) -> Option<T> {
+    Some(pow(radix, len.div_ceil(2))? - T::one())
+}
+
+fn pow<T: Integer + CheckedMul>(radix: &T, exp: u32) -> Option<T> {
+    (0..exp).try_fold(T::one(), |acc, _| acc.checked_mul(radix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{double_base_palindromes, PalindromeIter};
+    use crate::Integer;
+
+    const RADIX: u64 = 10;
+
+    #[test]
+    fn ascending() {
+        let expected = (0..100_000)
+            .filter(|n: &u64| n.is_palindromic(RADIX))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            expected,
+            PalindromeIter::new(RADIX)
+                .take_while(|&n| n < 100_000)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![0, 1, 3, 5, 7, 9, 15, 17, 21, 27],
+            PalindromeIter::new(2u32).take(10).collect::<Vec<_>>()
+        );
+
+        let expected = (0..=u16::MAX)
+            .filter(|n| n.is_palindromic(10))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, PalindromeIter::new(10u16).collect::<Vec<_>>());
+        assert_eq!(Some(252), PalindromeIter::new(10u8).max());
+    }
+
+    #[test]
+    fn with_digits() {
+        assert_eq!(
+            (0..10).collect::<Vec<_>>(),
+            PalindromeIter::with_digits(1, RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![99, 88, 77, 66, 55, 44, 33, 22, 11],
+            PalindromeIter::with_digits(2, RADIX)
+                .rev()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(900, PalindromeIter::with_digits(6, RADIX).count());
+        assert_eq!(
+            Some(99999),
+            PalindromeIter::with_digits(5, RADIX).next_back()
+        );
+        assert_eq!(Some(10001), PalindromeIter::with_digits(5, RADIX).next());
+    }
+
+    #[test]
+    fn range() {
+        for (lo, hi) in [
+            (0, 1000),
+            (10, 12),
+            (12, 21),
+            (100, 101),
+            (123, 4567),
+            (5, 5),
+            (8, 3),
+        ] {
+            let expected = (lo..hi)
+                .filter(|n: &u64| n.is_palindromic(RADIX))
+                .collect::<Vec<_>>();
+            let iter = PalindromeIter::range(lo..hi, RADIX);
+            assert_eq!(expected, iter.clone().collect::<Vec<_>>());
+            assert_eq!(
+                expected.into_iter().rev().collect::<Vec<_>>(),
+                iter.rev().collect::<Vec<_>>()
+            );
+        }
+
+        let mut iter = PalindromeIter::range(0..30, RADIX);
+        assert_eq!(Some(0), iter.next());
+        assert_eq!(Some(22), iter.next_back());
+        assert_eq!(10, iter.count());
+    }
+
+    #[test]
+    fn double_base() {
+        assert_eq!(
+            vec![0, 1, 3, 5, 7, 9, 33, 99, 313, 585, 717],
+            double_base_palindromes(0..1000, RADIX, 2).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::Integer;
+use std::ops::RangeInclusive;
+
+/// Evaluate if the concatenation of the numbers uses each digit in the range exactly once, and
+/// no other digits.
+pub fn is_pandigital_concat<T: Integer>(nums: &[T], radix: T, digits: RangeInclusive<u32>) -> bool {
+    let mut mask = 0;
+    for n in nums {
+        match n.digit_mask(radix.clone()) {
+            Some(m) if mask & m == 0 => mask |= m,
+            _ => return false,
+        }
+    }
+    mask == range_mask(digits)
+}
+
+/// Get the bitmask with a bit set for each digit in the range.
+#[inline]
+pub(crate) fn range_mask(digits: RangeInclusive<u32>) -> u64 {
+    let (lo, hi) = digits.into_inner();
+    assert!(
+        lo <= hi && hi < 64,
+        "Digits must be a non-empty range below 64"
+    );
+    (u64::MAX >> (63 - hi)) & (u64::MAX << lo)
+}
+
+/// An iterator which yields each number using every digit in a range exactly once, without a
+/// leading zero, in ascending or descending order. An empty range yields nothing.
+pub struct Pandigitals {
+    digits: Vec<u32>,
+    radix: u64,
+    descending: bool,
+    done: bool,
+}
+
+impl Pandigitals {
+    /// Create an iterator over the pandigital numbers for the digit range in ascending order.
+    pub fn ascending(digits: RangeInclusive<u32>, radix: u64) -> Self {
+        Pandigitals::new(digits.collect(), radix, false)
+    }
+
+    /// Create an iterator over the pandigital numbers for the digit range in descending order.
+    pub fn descending(digits: RangeInclusive<u32>, radix: u64) -> Self {
+        Pandigitals::new(digits.rev().collect(), radix, true)
+    }
+
+    fn new(mut digits: Vec<u32>, radix: u64, descending: bool) -> Self {
+        assert!(
+            digits.iter().all(|&d| (d as u64) < radix),
+            "Digits must be less than the radix"
+        );
+        assert!(
+            u32::try_from(digits.len())
+                .ok()
+                .and_then(|len| radix.checked_pow(len))
+                .is_some(),
+            "Pandigital numbers must fit in a u64"
+        );
+
+        //  Permutations are visited in lexicographic order of the digits, which matches the
+        //  numeric order since every permutation has the same length. In ascending order the
+        //  permutations with a leading zero come first, so the first is found by swapping the
+        //  smallest non-zero digit to the front.
+        //
+        if !descending && digits.len() > 1 && digits[0] == 0 {
+            digits.swap(0, 1);
+        }
+        let done = digits.is_empty() || (digits.len() > 1 && digits[0] == 0);
+        Pandigitals {
+            digits,
+            radix,
+            descending,
+            done,
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        let descending = self.descending;
+        let ordered = |a: u32, b: u32| if descending { a > b } else { a < b };
+
+        let Some(i) = (1..self.digits.len())
+            .rev()
+            .find(|&i| ordered(self.digits[i - 1], self.digits[i]))
+        else {
+            return false;
+        };
+        let j = (i..self.digits.len())
+            .rev()
+            .find(|&j| ordered(self.digits[i - 1], self.digits[j]))
+            .unwrap();
+
+        self.digits.swap(i - 1, j);
+        self.digits[i..].reverse();
+        !(self.digits.len() > 1 && self.digits[0] == 0)
+    }
+}
+
+impl Iterator for Pandigitals {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.done {
+            return None;
+        }
+
+        let n = self
+            .digits
+            .iter()
+            .fold(0, |acc, &d| acc * self.radix + d as u64);
+        self.done = !self.step();
+        Some(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_pandigital_concat, Pandigitals};
+    use crate::Integer;
+
+    const RADIX: u64 = 10;
+
+    #[test]
+    fn is_pandigital() {
+        assert!(123456789.is_pandigital(RADIX, 1..=9));
+        assert!(918273645.is_pandigital(RADIX, 1..=9));
+        assert!(1406357289.is_pandigital(RADIX, 0..=9));
+        assert!(2143.is_pandigital(RADIX, 1..=4));
+        assert!(0.is_pandigital(RADIX, 0..=0));
+        assert!(!1406357289.is_pandigital(RADIX, 1..=9));
+        assert!(!123456780.is_pandigital(RADIX, 1..=9));
+        assert!(!112345678.is_pandigital(RADIX, 1..=8));
+        assert!(!12345678.is_pandigital(RADIX, 1..=9));
+        assert!(0b10.is_pandigital(2, 0..=1));
+
+        assert_eq!(Some(0b1_0000_0111), 1_208u32.digit_mask(10));
+        assert_eq!(None, 1_211u32.digit_mask(10));
+    }
+
+    #[test]
+    fn concatenated() {
+        assert!(is_pandigital_concat(&[39, 186, 7254], RADIX, 1..=9));
+        assert!(is_pandigital_concat(&[192, 384, 576], RADIX, 1..=9));
+        assert!(!is_pandigital_concat(&[12, 21], RADIX, 1..=4));
+        assert!(!is_pandigital_concat(&[12, 34], RADIX, 1..=5));
+        assert!(is_pandigital_concat(&[0, 1], RADIX, 0..=1));
+    }
+
+    #[test]
+    fn generate() {
+        assert_eq!(
+            vec![123, 132, 213, 231, 312, 321],
+            Pandigitals::ascending(1..=3, RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![321, 312, 231, 213, 132, 123],
+            Pandigitals::descending(1..=3, RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![102, 120, 201, 210],
+            Pandigitals::ascending(0..=2, RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![210, 201, 120, 102],
+            Pandigitals::descending(0..=2, RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![0],
+            Pandigitals::ascending(0..=0, RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![0b10],
+            Pandigitals::descending(0..=1, 2).collect::<Vec<_>>()
+        );
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 1..=0;
+        assert_eq!(0, Pandigitals::ascending(empty.clone(), RADIX).count());
+        assert_eq!(0, Pandigitals::descending(empty, RADIX).count());
+        assert_eq!(
+            Some(0xfedcba987654321),
+            Pandigitals::descending(1..=15, 16).next()
+        );
+
+        assert_eq!(362880, Pandigitals::ascending(1..=9, RADIX).count());
+        assert_eq!(3265920, Pandigitals::descending(0..=9, RADIX).count());
+        assert!(Pandigitals::ascending(0..=7, RADIX).all(|n| n.is_pandigital(RADIX, 0..=7)));
+        assert_eq!(
+            Some(987654321),
+            Pandigitals::descending(1..=9, RADIX).next()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        Pandigitals::ascending(0..=15, 16);
+    }
+}
@@ -0,0 +1,502 @@
+use crate::{best_approximation, Integer};
+use num_traits::{CheckedAdd, CheckedMul, CheckedSub, One, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// An exact rational number, which is always kept in lowest terms with a positive denominator.
+/// Since the representation is canonical, equality and hashing compare the parts directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    denom: T,
+}
+
+impl<T: Integer> Ratio<T> {
+    /// Construct a ratio from a numerator and denominator, reducing it to lowest terms. Panics if
+    /// the denominator is zero.
+    pub fn new(numer: T, denom: T) -> Self {
+        assert!(!denom.is_zero(), "Denominator must be non-zero");
+        let (mut numer, mut denom) = (numer, denom);
+        if denom < T::zero() {
+            numer = T::zero() - numer;
+            denom = T::zero() - denom;
+        }
+
+        let gcd = numer.gcd(&denom);
+        Ratio {
+            numer: numer / gcd.clone(),
+            denom: denom / gcd,
+        }
+    }
+
+    /// Construct a ratio from parts which are already in lowest terms, with a positive denominator.
+    #[inline]
+    pub(crate) fn new_reduced(numer: T, denom: T) -> Self {
+        Ratio { numer, denom }
+    }
+
+    /// Construct a ratio which is equal to an integer.
+    #[inline]
+    pub fn from_integer(n: T) -> Self {
+        Ratio {
+            numer: n,
+            denom: T::one(),
+        }
+    }
+
+    /// Get the numerator, which carries the sign of the ratio.
+    #[inline]
+    pub fn numer(&self) -> &T {
+        &self.numer
+    }
+
+    /// Get the denominator, which is always positive.
+    #[inline]
+    pub fn denom(&self) -> &T {
+        &self.denom
+    }
+
+    /// Decompose the ratio into its numerator and denominator.
+    #[inline]
+    pub fn into_parts(self) -> (T, T) {
+        (self.numer, self.denom)
+    }
+
+    /// Evaluate if the ratio is an integer.
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.denom.is_one()
+    }
+
+    /// Get the reciprocal of the ratio. Panics if the ratio is zero.
+    #[inline]
+    pub fn recip(&self) -> Self {
+        Ratio::new(self.denom.clone(), self.numer.clone())
+    }
+
+    /// Get the largest integer less than or equal to the ratio.
+    #[inline]
+    pub fn floor(&self) -> T {
+        self.numer.div_floor(&self.denom)
+    }
+
+    /// Get the smallest integer greater than or equal to the ratio.
+    pub fn ceil(&self) -> T {
+        let (q, r) = self.numer.div_mod_floor(&self.denom);
+        if r.is_zero() {
+            q
+        } else {
+            q + T::one()
+        }
+    }
+
+    /// Get the fractional part of the ratio, `self - floor(self)`, which lies in `[0, 1)`.
+    #[inline]
+    pub fn fract(&self) -> Self {
+        //  gcd(a mod b, b) = gcd(a, b) = 1, so the remainder is already in lowest terms.
+        //
+        Ratio {
+            numer: self.numer.mod_floor(&self.denom),
+            denom: self.denom.clone(),
+        }
+    }
+
+    /// Get the mediant (a + c) / (b + d) of a / b and c / d, which lies between the two ratios.
+    #[inline]
+    pub fn mediant(&self, other: &Self) -> Self {
+        Ratio::new(
+            self.numer.clone() + other.numer.clone(),
+            self.denom.clone() + other.denom.clone(),
+        )
+    }
+
+    /// Add two ratios, or return `None` if any intermediate value overflows the integer type.
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self>
+    where
+        T: CheckedAdd + CheckedMul,
+    {
+        let (l, r) = self.lcm_factors(rhs);
+        let numer = self
+            .numer
+            .checked_mul(&l)?
+            .checked_add(&rhs.numer.checked_mul(&r)?)?;
+        Some(Ratio::new(numer, self.denom.checked_mul(&l)?))
+    }
+
+    /// Subtract two ratios, or return `None` if any intermediate value overflows the integer type.
+    /// For unsigned types this includes a negative difference.
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self>
+    where
+        T: CheckedSub + CheckedMul,
+    {
+        let (l, r) = self.lcm_factors(rhs);
+        let numer = self
+            .numer
+            .checked_mul(&l)?
+            .checked_sub(&rhs.numer.checked_mul(&r)?)?;
+        Some(Ratio::new(numer, self.denom.checked_mul(&l)?))
+    }
+
+    /// Multiply two ratios, or return `None` if the product overflows the integer type.
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self>
+    where
+        T: CheckedMul,
+    {
+        let (a, b, c, d) = cross_reduce(self, rhs);
+        Some(Ratio::new(a.checked_mul(&c)?, b.checked_mul(&d)?))
+    }
+
+    /// Divide two ratios, or return `None` if the divisor is zero or the quotient overflows the
+    /// integer type.
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self>
+    where
+        T: CheckedMul,
+    {
+        if rhs.numer.is_zero() {
+            return None;
+        }
+        self.checked_mul(&rhs.recip())
+    }
+
+    /// Expand the ratio into its partial quotients [a0; a1, a2, ...]. The expansion is finite and
+    /// its last term is greater than one, unless the ratio is an integer.
+    pub fn to_continued_fraction(&self) -> Vec<T> {
+        let mut terms = Vec::new();
+        let (mut a, mut b) = (self.numer.clone(), self.denom.clone());
+        loop {
+            let (q, r) = a.div_mod_floor(&b);
+            terms.push(q);
+            if r.is_zero() {
+                return terms;
+            }
+            (a, b) = (b, r);
+        }
+    }
+
+    /// Evaluate the continued fraction [a0; a1, a2, ...]. Panics if there are no terms.
+    pub fn from_continued_fraction(terms: &[T]) -> Self {
+        let (last, rest) = terms
+            .split_last()
+            .expect("Continued fraction must be non-empty");
+        let (numer, denom) = rest
+            .iter()
+            .rev()
+            .fold((last.clone(), T::one()), |(n, d), a| {
+                (a.clone() * n.clone() + d, n)
+            });
+        Ratio::new(numer, denom)
+    }
+
+    /// Find the closest ratio to this one with a denominator of at most `max_denom`.
+    #[inline]
+    pub fn best_approximation(&self, max_denom: T) -> Self {
+        best_approximation(self.to_continued_fraction(), max_denom)
+    }
+
+    /// Evaluate if the ratio has a terminating expansion in the radix, which is the case when
+    /// every prime factor of the denominator divides the radix.
+    pub fn is_terminating(&self, radix: T) -> bool {
+        let mut denom = self.denom.clone();
+        loop {
+            let gcd = denom.gcd(&radix);
+            if gcd.is_one() {
+                return denom.is_one();
+            }
+            denom = denom / gcd;
+        }
+    }
+
+    /// Compute the exact expansion of the ratio in the radix, truncated after `places` fractional
+    /// digits. Returns the integer part, which is the floor, and the digits of the fractional
+    /// part. Fewer digits are returned if the expansion terminates early.
+    pub fn expansion(&self, radix: T, places: usize) -> (T, Vec<T>) {
+        let (int, mut rem) = self.numer.div_mod_floor(&self.denom);
+        let mut digits = Vec::new();
+        while !rem.is_zero() && digits.len() < places {
+            let (d, r) = (rem * radix.clone()).div_rem(&self.denom);
+            digits.push(d);
+            rem = r;
+        }
+        (int, digits)
+    }
+
+    /// Get the factors `(d / g, b / g)` which scale a / b and c / d to the common denominator
+    /// lcm(b, d), where g = gcd(b, d).
+    fn lcm_factors(&self, rhs: &Self) -> (T, T) {
+        let gcd = self.denom.gcd(&rhs.denom);
+        (rhs.denom.clone() / gcd.clone(), self.denom.clone() / gcd)
+    }
+}
+
+/// Cancel common factors across a / b and c / d before multiplying, so that the intermediate
+/// products are as small as possible.
+fn cross_reduce<T: Integer>(lhs: &Ratio<T>, rhs: &Ratio<T>) -> (T, T, T, T) {
+    let g1 = lhs.numer.gcd(&rhs.denom);
+    let g2 = rhs.numer.gcd(&lhs.denom);
+    (
+        lhs.numer.clone() / g1.clone(),
+        lhs.denom.clone() / g2.clone(),
+        rhs.numer.clone() / g2,
+        rhs.denom.clone() / g1,
+    )
+}
+
+impl<T: Integer> Add for Ratio<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let (l, r) = self.lcm_factors(&rhs);
+        Ratio::new(self.numer * l.clone() + rhs.numer * r, self.denom * l)
+    }
+}
+
+impl<T: Integer> Sub for Ratio<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (l, r) = self.lcm_factors(&rhs);
+        Ratio::new(self.numer * l.clone() - rhs.numer * r, self.denom * l)
+    }
+}
+
+impl<T: Integer> Mul for Ratio<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b, c, d) = cross_reduce(&self, &rhs);
+        Ratio::new(a * c, b * d)
+    }
+}
+
+impl<T: Integer> Div for Ratio<T> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.recip()
+    }
+}
+
+impl<T: Integer + Neg<Output = T>> Neg for Ratio<T> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Ratio {
+            numer: -self.numer,
+            denom: self.denom,
+        }
+    }
+}
+
+impl<T: Integer> Ord for Ratio<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        //  Cross multiplication may overflow, so instead compare the continued fraction
+        //  expansions term by term. The order of the remainders flips at each step, since they are
+        //  compared through their reciprocals.
+        //
+        let (mut a, mut b) = (self.numer.clone(), self.denom.clone());
+        let (mut c, mut d) = (other.numer.clone(), other.denom.clone());
+        let mut flipped = false;
+        loop {
+            let (q1, r1) = a.div_mod_floor(&b);
+            let (q2, r2) = c.div_mod_floor(&d);
+            let ordering = match (r1.is_zero(), r2.is_zero()) {
+                _ if q1 != q2 => q1.cmp(&q2),
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                (false, false) => {
+                    (a, b, c, d) = (b, r1, d, r2);
+                    flipped = !flipped;
+                    continue;
+                }
+            };
+            return if flipped {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+impl<T: Integer> PartialOrd for Ratio<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer> From<T> for Ratio<T> {
+    #[inline]
+    fn from(n: T) -> Self {
+        Ratio::from_integer(n)
+    }
+}
+
+impl<T: Integer> Zero for Ratio<T> {
+    #[inline]
+    fn zero() -> Self {
+        Ratio::from_integer(T::zero())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+}
+
+impl<T: Integer> One for Ratio<T> {
+    #[inline]
+    fn one() -> Self {
+        Ratio::from_integer(T::one())
+    }
+}
+
+impl<T: Integer> Sum for Ratio<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, n| acc + n)
+    }
+}
+
+impl<T: Integer> Product for Ratio<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, n| acc * n)
+    }
+}
+
+impl<T: Integer + fmt::Display> fmt::Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denom.is_one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ratio;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn reduction() {
+        assert_eq!((1, 2), Ratio::new(6, 12).into_parts());
+        assert_eq!((-3, 4), Ratio::new(6, -8).into_parts());
+        assert_eq!((0, 1), Ratio::new(0, -5).into_parts());
+        assert_eq!(Ratio::new(2u32, 3), Ratio::new(10, 15));
+        assert_eq!("7/3", Ratio::new(14, 6).to_string());
+        assert_eq!("-5", Ratio::new(10, -2).to_string());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (Ratio::new(1i64, 6), Ratio::new(3, 10));
+        assert_eq!(Ratio::new(7, 15), a + b);
+        assert_eq!(Ratio::new(-2, 15), a - b);
+        assert_eq!(Ratio::new(1, 20), a * b);
+        assert_eq!(Ratio::new(5, 9), a / b);
+        assert_eq!(Ratio::new(-1, 6), -a);
+        assert_eq!(
+            Ratio::from_integer(1),
+            (1..=10)
+                .map(|n| Ratio::new(n, n + 1))
+                .product::<Ratio<i64>>()
+                * Ratio::from(11)
+        );
+        assert_eq!(
+            Ratio::new(7381, 2520),
+            (1..=10).map(|n| Ratio::new(1, n)).sum::<Ratio<i64>>()
+        );
+
+        let big = Ratio::new(BigUint::from(1u32), BigUint::from(3u32));
+        let sum = (0..3).map(|_| big.clone()).sum::<Ratio<BigUint>>();
+        assert!(sum.is_integer());
+    }
+
+    #[test]
+    fn checked() {
+        let half = Ratio::new(1u8, 2);
+        let third = Ratio::new(1u8, 3);
+        assert_eq!(Some(Ratio::new(5, 6)), half.checked_add(&third));
+        assert_eq!(Some(Ratio::new(1, 6)), half.checked_sub(&third));
+        assert_eq!(None, third.checked_sub(&half));
+        assert_eq!(None, half.checked_div(&Ratio::from(0)));
+
+        let small = Ratio::new(1u8, 200);
+        assert_eq!(None, small.checked_mul(&Ratio::new(1, 3)));
+        assert_eq!(
+            Some(Ratio::new(1, 100)),
+            small.checked_mul(&Ratio::new(2, 1))
+        );
+        assert_eq!(None, small.checked_add(&Ratio::new(1, 3)));
+    }
+
+    #[test]
+    fn ordering() {
+        let mut ratios = [
+            Ratio::new(3i64, 7),
+            Ratio::new(-1, 2),
+            Ratio::new(2, 5),
+            Ratio::new(1, 1),
+            Ratio::new(5, 12),
+            Ratio::new(-2, 3),
+            Ratio::new(0, 1),
+        ];
+        ratios.sort();
+        assert_eq!(
+            vec!["-2/3", "-1/2", "0", "2/5", "5/12", "3/7", "1"],
+            ratios.iter().map(|r| r.to_string()).collect::<Vec<_>>()
+        );
+
+        //  Cross multiplication of these would overflow a u64.
+        let a = Ratio::new(u64::MAX - 1, u64::MAX);
+        let b = Ratio::new(u64::MAX - 2, u64::MAX - 1);
+        assert!(b < a);
+        assert!(Ratio::new(3, 7).mediant(&Ratio::new(2, 5)) < Ratio::new(3, 7));
+        assert_eq!(
+            Ratio::new(5, 12),
+            Ratio::new(3, 7).mediant(&Ratio::new(2, 5))
+        );
+    }
+
+    #[test]
+    fn floor_and_ceil() {
+        assert_eq!(2, Ratio::new(7, 3).floor());
+        assert_eq!(3, Ratio::new(7, 3).ceil());
+        assert_eq!(-3, Ratio::new(-7, 3).floor());
+        assert_eq!(-2, Ratio::new(-7, 3).ceil());
+        assert_eq!(4, Ratio::new(8, 2).ceil());
+        assert_eq!(Ratio::new(2, 3), Ratio::new(-7, 3).fract());
+    }
+
+    #[test]
+    fn continued_fraction() {
+        assert_eq!(vec![3, 7, 16], Ratio::new(355, 113).to_continued_fraction());
+        assert_eq!(vec![-1, 1, 2], Ratio::new(-1, 3).to_continued_fraction());
+        assert_eq!(vec![5], Ratio::from_integer(5).to_continued_fraction());
+        assert_eq!(
+            Ratio::new(355, 113),
+            Ratio::from_continued_fraction(&[3, 7, 15, 1])
+        );
+        assert_eq!(
+            Ratio::new(-1, 3),
+            Ratio::from_continued_fraction(&[-1, 1, 2])
+        );
+    }
+
+    #[test]
+    fn expansion() {
+        assert!(Ratio::new(3u64, 40).is_terminating(10));
+        assert!(!Ratio::new(1u64, 12).is_terminating(10));
+        assert!(Ratio::new(1u64, 12).is_terminating(6));
+        assert_eq!((0, vec![0, 7, 5]), Ratio::new(3u64, 40).expansion(10, 10));
+        assert_eq!((2, vec![1, 4, 2, 8]), Ratio::new(15u64, 7).expansion(10, 4));
+        assert_eq!((-1, vec![7, 5]), Ratio::new(-1i64, 4).expansion(10, 10));
+        assert_eq!((0, vec![0, 1, 0, 1]), Ratio::new(1u64, 3).expansion(2, 4));
+    }
+}
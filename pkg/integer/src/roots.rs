@@ -0,0 +1,128 @@
+use crate::Integer;
+use num_integer::Roots;
+use num_traits::CheckedMul;
+
+//  A perfect square must be a quadratic residue modulo every m. Testing a few small moduli with
+//  precomputed residue bitmasks rejects all but about 1 in 100 non-squares before any root is
+//  computed. The moduli are chosen so that 64 * 63 * 65 * 11 still fits in a u32.
+//
+const SQUARES_MOD_64: u128 = residue_mask(64);
+const SQUARES_MOD_63: u128 = residue_mask(63);
+const SQUARES_MOD_65: u128 = residue_mask(65);
+const SQUARES_MOD_11: u128 = residue_mask(11);
+
+const fn residue_mask(m: u64) -> u128 {
+    let mut mask = 0;
+    let mut i = 0;
+    while i < m {
+        mask |= 1 << (i * i % m);
+        i += 1;
+    }
+    mask
+}
+
+pub(crate) fn is_perfect_square<T: Integer + Roots>(n: &T) -> bool {
+    if *n < T::zero() {
+        return false;
+    }
+
+    let residue = match T::from_u32(64 * 63 * 65 * 11) {
+        Some(m) => n.clone() % m,
+        None => n.clone(),
+    };
+    let residue = residue.to_u64().unwrap();
+    let is_residue = |mask: u128, m: u64| mask & (1 << (residue % m)) != 0;
+    if !(is_residue(SQUARES_MOD_64, 64)
+        && is_residue(SQUARES_MOD_63, 63)
+        && is_residue(SQUARES_MOD_65, 65)
+        && is_residue(SQUARES_MOD_11, 11))
+    {
+        return false;
+    }
+
+    let root = Roots::sqrt(n);
+    root.clone() * root == *n
+}
+
+pub(crate) fn is_perfect_power<T: Integer + Roots>(n: &T) -> Option<(T, u32)> {
+    if *n < T::from_u8(2).unwrap() {
+        return None;
+    }
+
+    //  Checking exponents from the largest possible downwards finds the decomposition with the
+    //  largest exponent, and therefore the smallest base.
+    //
+    let bits = n.num_digits(T::from_u8(2).unwrap()) as u32;
+    (2..bits).rev().find_map(|exp| {
+        let root = Roots::nth_root(n, exp);
+        let power = num_traits::pow(root.clone(), exp as usize);
+        (power == *n).then_some((root, exp))
+    })
+}
+
+pub(crate) fn checked_power<T: Integer + CheckedMul>(n: &T, exp: u32) -> Option<T> {
+    num_traits::checked_pow(n.clone(), exp as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Integer;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn roots() {
+        assert_eq!(31, 1000u64.integer_sqrt());
+        assert_eq!(10, 1000u64.integer_cbrt());
+        assert_eq!(3, 1000u64.integer_nth_root(6));
+        assert_eq!(4_294_967_295, u64::MAX.integer_sqrt());
+        assert_eq!(-3, (-27i32).integer_cbrt());
+
+        let n = BigUint::from(10u32).pow(40) + BigUint::from(1u32);
+        assert_eq!(BigUint::from(10u32).pow(20), n.integer_sqrt());
+        assert_eq!(BigUint::from(10u32).pow(10), n.integer_nth_root(4));
+    }
+
+    #[test]
+    fn is_perfect_square() {
+        let squares = (0..100u64).map(|n| n * n).collect::<Vec<_>>();
+        for n in 0..10_000u64 {
+            assert_eq!(squares.contains(&n), n.is_perfect_square());
+        }
+
+        assert!(!(-4i32).is_perfect_square());
+        assert!(4_294_967_295u64.pow(2).is_perfect_square());
+        assert!(!(4_294_967_295u64.pow(2) + 1).is_perfect_square());
+        assert!(BigUint::from(12345u32).pow(8).is_perfect_square());
+        assert!(!(BigUint::from(12345u32).pow(8) - 1u32).is_perfect_square());
+    }
+
+    #[test]
+    fn is_perfect_power() {
+        assert_eq!(None, 0u32.is_perfect_power());
+        assert_eq!(None, 1u32.is_perfect_power());
+        assert_eq!(None, 2u32.is_perfect_power());
+        assert_eq!(None, 12u32.is_perfect_power());
+        assert_eq!(Some((2, 2)), 4u32.is_perfect_power());
+        assert_eq!(Some((2, 6)), 64u32.is_perfect_power());
+        assert_eq!(Some((6, 2)), 36u32.is_perfect_power());
+        assert_eq!(Some((10, 3)), 1000u32.is_perfect_power());
+        assert_eq!(Some((3, 40)), 3u64.pow(40).is_perfect_power());
+        assert_eq!(Some((3, 4)), 81u8.is_perfect_power());
+        assert_eq!(Some((2, 63)), (1u64 << 63).is_perfect_power());
+        assert_eq!(
+            Some((BigUint::from(7u32), 100)),
+            BigUint::from(7u32).pow(100).is_perfect_power()
+        );
+    }
+
+    #[test]
+    fn checked_power() {
+        assert_eq!(Some(1024), 2u16.checked_power(10));
+        assert_eq!(None, 2u16.checked_power(16));
+        assert_eq!(Some(1), 0u8.checked_power(0));
+        assert_eq!(
+            Some(BigUint::from(2u32).pow(100)),
+            BigUint::from(2u32).checked_power(100)
+        );
+    }
+}
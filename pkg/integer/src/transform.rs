@@ -0,0 +1,236 @@
+use crate::Integer;
+use num_traits::{CheckedAdd, CheckedMul};
+
+/// An iterator which yields each rotation of the digits of an integer, starting with the integer
+/// itself and moving the leading digit to the end each step. A rotation which overflows the
+/// integer type is skipped, and the iteration continues with the next rotation.
+#[derive(Clone)]
+pub struct Rotations<T> {
+    num: T,
+    /// The power of the radix splitting off the digits moved to the end.
+    split: T,
+    /// The power of the radix which shifts the remaining digits to the front.
+    shift: T,
+    radix: T,
+    index: usize,
+    len: usize,
+}
+
+impl<T: Integer> Rotations<T> {
+    pub(crate) fn new(num: T, radix: T) -> Self {
+        let len = num.num_digits(radix.clone());
+        let split = leading_order(&num, &radix);
+        Rotations {
+            num,
+            split,
+            shift: radix.clone(),
+            radix,
+            index: 0,
+            len,
+        }
+    }
+}
+
+impl<T: Integer + CheckedAdd + CheckedMul> Iterator for Rotations<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index == 0 {
+            self.index = 1;
+            return Some(self.num.clone());
+        }
+
+        //  Each rotation is built from the original integer, so that skipping one which overflows
+        //  does not lose the ones after it. The split is fixed to the leading digit of the
+        //  original integer, so rotating a zero into the leading position keeps its place, e.g.
+        //  101 -> 011 -> 110. Both powers stay below the leading order, so only the rotation
+        //  itself can overflow.
+        //
+        while self.index < self.len {
+            let (lead, rest) = self.num.div_rem(&self.split);
+            let rotation = rest
+                .checked_mul(&self.shift)
+                .and_then(|n| n.checked_add(&lead));
+            self.index += 1;
+            if self.index < self.len {
+                self.split = self.split.clone() / self.radix.clone();
+                self.shift = self.shift.clone() * self.radix.clone();
+            }
+            if rotation.is_some() {
+                return rotation;
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        ((self.index == 0) as usize, Some(self.len - self.index))
+    }
+}
+
+/// An iterator which yields an integer followed by each truncation formed by removing digits
+/// from the left, e.g. 3797, 797, 97, 7. Each removed digit yields one truncation, so a zero
+/// exposed as the leading digit repeats the previous value, e.g. 103, 3, 3, which keeps the count
+/// equal to the number of digits as for the other digit iterators.
+#[derive(Clone)]
+pub struct LeftTruncations<T> {
+    num: T,
+    radix: T,
+    order: T,
+    first: bool,
+}
+
+impl<T: Integer> LeftTruncations<T> {
+    pub(crate) fn new(num: T, radix: T) -> Self {
+        let order = leading_order(&num, &radix);
+        LeftTruncations {
+            num,
+            radix,
+            order,
+            first: true,
+        }
+    }
+}
+
+impl<T: Integer> Iterator for LeftTruncations<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if std::mem::take(&mut self.first) {
+            return Some(self.num.clone());
+        }
+        if self.order.is_one() {
+            return None;
+        }
+
+        self.num = self.num.mod_floor(&self.order);
+        self.order = self.order.clone() / self.radix.clone();
+        Some(self.num.clone())
+    }
+}
+
+/// An iterator which yields an integer followed by each truncation formed by removing digits
+/// from the right, e.g. 3797, 379, 37, 3.
+#[derive(Clone)]
+pub struct RightTruncations<T> {
+    num: T,
+    radix: T,
+    first: bool,
+}
+
+impl<T: Integer> RightTruncations<T> {
+    pub(crate) fn new(num: T, radix: T) -> Self {
+        RightTruncations {
+            num,
+            radix,
+            first: true,
+        }
+    }
+}
+
+impl<T: Integer> Iterator for RightTruncations<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if std::mem::take(&mut self.first) {
+            return Some(self.num.clone());
+        }
+
+        self.num = self.num.clone() / self.radix.clone();
+        if self.num.is_zero() {
+            None
+        } else {
+            Some(self.num.clone())
+        }
+    }
+}
+
+/// Get the order of magnitude of the leading digit, which is one for zero.
+fn leading_order<T: Integer>(num: &T, radix: &T) -> T {
+    let mut order = T::one();
+    let bound = num.clone() / radix.clone();
+    while order <= bound {
+        order = order * radix.clone();
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Integer;
+
+    const RADIX: u64 = 10;
+
+    #[test]
+    fn rotations() {
+        assert_eq!(
+            vec![197, 971, 719],
+            197.rotations(RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![101, 11, 110], 101.rotations(RADIX).collect::<Vec<_>>());
+        assert_eq!(vec![7], 7.rotations(RADIX).collect::<Vec<_>>());
+        assert_eq!(vec![0], 0.rotations(RADIX).collect::<Vec<_>>());
+        assert_eq!(
+            vec![0b110, 0b101, 0b011],
+            0b110.rotations(2).collect::<Vec<_>>()
+        );
+        assert_eq!(10, 1_234_567_890u64.rotations(RADIX).count());
+        assert_eq!(vec![200, 2, 20], 200u8.rotations(10).collect::<Vec<_>>());
+        assert_eq!(vec![250, 25], 250u8.rotations(10).collect::<Vec<_>>());
+        assert_eq!(vec![u8::MAX], u8::MAX.rotations(10).collect::<Vec<_>>());
+        assert_eq!(
+            vec![
+                u64::MAX,
+                7_370_955_161_518_446_744,
+                9_551_615_184_467_440_737,
+                16_151_844_674_407_370_955,
+                15_184_467_440_737_095_516
+            ],
+            u64::MAX.rotations(RADIX).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn truncations() {
+        assert_eq!(
+            vec![3797, 797, 97, 7],
+            3797.left_truncations(RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![3797, 379, 37, 3],
+            3797.right_truncations(RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![103, 3, 3],
+            103.left_truncations(RADIX).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0], 0.left_truncations(RADIX).collect::<Vec<_>>());
+        assert_eq!(vec![0], 0.right_truncations(RADIX).collect::<Vec<_>>());
+        assert_eq!(20, u64::MAX.left_truncations(RADIX).count());
+        assert_eq!(20, u64::MAX.right_truncations(RADIX).count());
+    }
+
+    #[test]
+    fn reverse_and_concat() {
+        assert_eq!(4321, 1234.reverse_digits(RADIX));
+        assert_eq!(1, 100.reverse_digits(RADIX));
+        assert_eq!(0, 0.reverse_digits(RADIX));
+        assert_eq!(0b011, 0b110.reverse_digits(2));
+
+        assert_eq!(12345, 12.concat(345, RADIX));
+        assert_eq!(120, 12.concat(0, RADIX));
+        assert_eq!(7, 0.concat(7, RADIX));
+        assert_eq!(0b1011, 0b10.concat(0b11, 2));
+
+        assert_eq!(Some(4321), 1234.checked_reverse_digits(RADIX));
+        assert_eq!(Some(91), 190u8.checked_reverse_digits(10));
+        assert_eq!(None, 199u8.checked_reverse_digits(10));
+        assert_eq!(None, u64::MAX.checked_reverse_digits(RADIX));
+        assert_eq!(Some(12345), 12.checked_concat(345, RADIX));
+        assert_eq!(Some(255), 25u8.checked_concat(5, 10));
+        assert_eq!(None, 25u8.checked_concat(6, 10));
+        assert_eq!(None, 2u8.checked_concat(56, 10));
+        assert_eq!(None, u64::MAX.checked_concat(0, RADIX));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{Factorize, PrimeSeq};
+use integer::{crt, mod_inv, mod_pow, mul_mod};
+
+/// Binomial coefficients modulo a prime, computed using precomputed factorial tables and
+/// Lucas' theorem. The tables are computed once, so this is suited to answering many queries
+/// against the same modulus.
+pub struct BinomialMod {
+    p: u64,
+    fact: Vec<u64>,
+    inv_fact: Vec<u64>,
+}
+
+impl BinomialMod {
+    /// Precompute factorial tables modulo the prime `p`, sufficient for any `n <= limit`. The
+    /// tables never exceed `p` entries, since Lucas' theorem reduces larger arguments.
+    pub fn new(p: u64, limit: u64) -> Self {
+        assert!(p >= 2, "Modulus must be prime");
+
+        let len = (limit.min(p - 1) + 1) as usize;
+        let mut fact = vec![1; len];
+        for i in 1..len {
+            fact[i] = mul_mod(fact[i - 1], i as u64, p);
+        }
+
+        let mut inv_fact = vec![1; len];
+        inv_fact[len - 1] = mod_inv(fact[len - 1], p).expect("Modulus must be prime");
+        for i in (1..len).rev() {
+            inv_fact[i - 1] = mul_mod(inv_fact[i], i as u64, p);
+        }
+
+        BinomialMod { p, fact, inv_fact }
+    }
+
+    /// Calculate the combination nCr modulo the prime.
+    pub fn combinations(&self, mut n: u64, mut k: u64) -> u64 {
+        assert!(n >= k);
+
+        //  Lucas' theorem states that nCk is congruent to the product of the binomials of the
+        //  base p digits of n and k:
+        //      nCk ≡ (n0 C k0)(n1 C k1)... (mod p)
+        //  Each digit binomial is evaluated using the factorial tables.
+        //
+        let mut result = 1 % self.p;
+        while k > 0 {
+            let (ni, ki) = (n % self.p, k % self.p);
+            if ki > ni {
+                return 0;
+            }
+
+            let (ni, ki) = (ni as usize, ki as usize);
+            result = mul_mod(result, self.fact[ni], self.p);
+            result = mul_mod(result, self.inv_fact[ki], self.p);
+            result = mul_mod(result, self.inv_fact[ni - ki], self.p);
+            n /= self.p;
+            k /= self.p;
+        }
+        result
+    }
+}
+
+/// Calculate the combination nCr modulo any positive integer.
+pub(crate) fn combinations_mod(ps: &PrimeSeq, n: u64, k: u64, m: u64) -> u64 {
+    assert!(n >= k);
+    assert!(m >= 1, "Modulus must be positive");
+
+    //  The modulus is split into prime powers, the binomial is computed modulo each prime power
+    //  independently, and the residues are recombined using the Chinese remainder theorem.
+    //
+    let congruences = m
+        .factorize(ps)
+        .map(|factor| {
+            let q = factor.base.pow(factor.exp as u32);
+            let r = if factor.exp == 1 {
+                combinations_mod_prime(n, k, factor.base)
+            } else {
+                combinations_mod_prime_power(n, k, factor.base, q)
+            };
+            (r, q)
+        })
+        .collect::<Vec<_>>();
+
+    crt(&congruences).unwrap().0
+}
+
+/// Calculate the combination nCr modulo the prime `p` using Lucas' theorem. Unlike `BinomialMod`
+/// no tables are built, so a single query against a large prime needs no memory, and each digit
+/// binomial takes O(min(k, n - k)) steps.
+fn combinations_mod_prime(mut n: u64, mut k: u64, p: u64) -> u64 {
+    let mut result = 1 % p;
+    while k > 0 {
+        let (ni, ki) = (n % p, k % p);
+        if ki > ni {
+            return 0;
+        }
+
+        let ki = ki.min(ni - ki);
+        let (mut num, mut den) = (1, 1);
+        for i in 0..ki {
+            num = mul_mod(num, ni - i, p);
+            den = mul_mod(den, i + 1, p);
+        }
+        result = mul_mod(result, mul_mod(num, mod_inv(den, p).unwrap(), p), p);
+        n /= p;
+        k /= p;
+    }
+    result
+}
+
+/// Calculate the permutation nPr modulo any positive integer.
+pub(crate) fn permutations_mod(n: u64, k: u64, m: u64) -> u64 {
+    assert!(n >= k);
+    assert!(m >= 1, "Modulus must be positive");
+    ((n - k + 1)..=n).fold(1 % m, |acc, i| mul_mod(acc, i % m, m))
+}
+
+/// Calculate the combination nCr modulo the prime power `q = p^e`. This takes O(q log n) time
+/// in the worst case, so it is only practical for moderately sized prime powers.
+fn combinations_mod_prime_power(n: u64, k: u64, p: u64, q: u64) -> u64 {
+    //  This follows Granville's generalization of Lucas' theorem. Each factorial is split into
+    //  a power of p, counted using Legendre's formula, and a p-free part which is a unit modulo
+    //  q. The p-free part of n! is computed recursively, since the product of the units in each
+    //  complete block of q consecutive integers is the same:
+    //      n!_p ≡ (q!_p)^(n / q) * (n mod q)!_p * (n / p)!_p (mod q)
+    //
+    let v = legendre(n, p) - legendre(k, p) - legendre(n - k, p);
+    let mut p_pow = 1u64;
+    for _ in 0..v {
+        p_pow *= p;
+        if p_pow >= q {
+            return 0;
+        }
+    }
+
+    //  The product of all units modulo q is -1, except for powers of two from 8 upwards where it
+    //  is 1, by Gauss' generalization of Wilson's theorem. Only the partial block is multiplied
+    //  out, so no table of size q is needed.
+    //
+    let block = if p == 2 && q >= 8 { 1 } else { q - 1 };
+    let partial = |r: u64| {
+        (1..=r)
+            .filter(|i| !i.is_multiple_of(p))
+            .fold(1 % q, |acc, i| mul_mod(acc, i, q))
+    };
+    let p_free_factorial = |mut n: u64| {
+        let mut result = 1 % q;
+        while n > 0 {
+            result = mul_mod(result, mod_pow(block, n / q, q), q);
+            result = mul_mod(result, partial(n % q), q);
+            n /= p;
+        }
+        result
+    };
+
+    let num = p_free_factorial(n);
+    let den = mul_mod(p_free_factorial(k), p_free_factorial(n - k), q);
+    mul_mod(mul_mod(num, mod_inv(den, q).unwrap(), q), p_pow, q)
+}
+
+/// Compute the exponent of the prime p in n!, using Legendre's formula.
+fn legendre(mut n: u64, p: u64) -> u64 {
+    let mut v = 0;
+    while n > 0 {
+        n /= p;
+        v += n;
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinomialMod;
+    use crate::PrimeSeq;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn binomial_mod_prime() {
+        let ps = PrimeSeq::new();
+        for p in [2, 3, 7, 13, 1_000_000_007] {
+            let table = BinomialMod::new(p, 60);
+            for n in 0..=60 {
+                for k in 0..=n {
+                    let expected = ps.combinations_big(n, k) % BigUint::from(p);
+                    assert_eq!(expected, BigUint::from(table.combinations(n, k)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn combinations_mod() {
+        let ps = PrimeSeq::new();
+        for m in [1, 8, 9, 12, 100, 142857, 1_000_000_007] {
+            for n in 0..=50 {
+                for k in 0..=n {
+                    let expected = ps.combinations_big(n, k) % BigUint::from(m);
+                    assert_eq!(expected, BigUint::from(ps.combinations_mod(n, k, m)));
+                }
+            }
+        }
+
+        assert_eq!(ps.combinations_mod(1_000, 500, 1_000_000_007), 159835829);
+        assert_eq!(
+            ps.combinations_mod(1_000_000_000_000, 2, 1_000_000_007),
+            ((1_000_000_000_000u128 * 999_999_999_999 / 2) % 1_000_000_007) as u64
+        );
+        assert_eq!(ps.combinations_mod(1_000_000, 12_345, 390_625), 34_375);
+        assert_eq!(ps.combinations_mod(1_000_000, 12_345, 84_934_656), 45_225_216);
+    }
+
+    #[test]
+    fn permutations_mod() {
+        let ps = PrimeSeq::new();
+        for m in [1, 12, 1_000_000_007] {
+            for n in 0..=40 {
+                for k in 0..=n {
+                    let expected = ps.permutations_big(n, k) % BigUint::from(m);
+                    assert_eq!(expected, BigUint::from(ps.permutations_mod(n, k, m)));
+                }
+            }
+        }
+    }
+}
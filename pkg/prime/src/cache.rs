@@ -0,0 +1,194 @@
+use crate::store::PrimeStore;
+use std::{
+    fs,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const MAGIC: &[u8; 4] = b"PRMS";
+const VERSION: u32 = 2;
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Write a list of primes to a compact binary file.
+///
+/// The file consists of a header (magic, version and prime count), followed by the halved gaps
+/// between consecutive odd primes encoded as LEB128 varints, and finally an FNV-1a checksum of
+/// the header and the encoded gaps. Nearly all gaps below 2<sup>64</sup> encode to one or two
+/// bytes.
+pub(crate) fn write<P: AsRef<Path>>(primes: &PrimeStore, path: P) -> io::Result<()> {
+    assert!(primes.len() >= 2 && primes.get(0) == 2 && primes.get(1) == 3);
+
+    let mut bytes = Vec::with_capacity(primes.len() + 16);
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(primes.len() as u64).to_le_bytes());
+    for (p, q) in primes.iter().skip(1).zip(primes.iter().skip(2)) {
+        write_varint(&mut bytes, (q - p) / 2);
+    }
+
+    let mut writer = BufWriter::new(fs::File::create(path)?);
+    writer.write_all(&bytes)?;
+    writer.write_all(&checksum(&bytes).to_le_bytes())?;
+    writer.flush()
+}
+
+/// Read a list of primes from a binary file written by `write`.
+pub(crate) fn read<P: AsRef<Path>>(path: P) -> io::Result<PrimeStore> {
+    let mut bytes = Vec::new();
+    BufReader::new(fs::File::open(path)?).read_to_end(&mut bytes)?;
+
+    if bytes.len() < 24 || &bytes[0..4] != MAGIC {
+        return Err(invalid_data("Not a prime cache file"));
+    }
+    if u32::from_le_bytes(bytes[4..8].try_into().unwrap()) != VERSION {
+        return Err(invalid_data("Unsupported prime cache version"));
+    }
+
+    let (data, tail) = bytes.split_at(bytes.len() - 8);
+    if checksum(data) != u64::from_le_bytes(tail.try_into().unwrap()) {
+        return Err(invalid_data("Prime cache checksum mismatch"));
+    }
+
+    let count = u64::from_le_bytes(data[8..16].try_into().unwrap()) as usize;
+    if count < 2 {
+        return Err(invalid_data("Prime cache must hold at least two primes"));
+    }
+    let body = &data[16..];
+
+    //  Every gap takes at least one byte, which bounds the capacity for a corrupt count.
+    //
+    let mut primes = PrimeStore::with_capacity(count.min(body.len() + 2));
+    primes.extend([2, 3]);
+    let mut body = body.iter();
+    while primes.len() < count {
+        let gap = read_varint(&mut body).ok_or_else(|| invalid_data("Truncated prime cache"))?;
+        if gap == 0 {
+            return Err(invalid_data("Prime cache primes must be strictly ascending"));
+        }
+        let p = gap
+            .checked_mul(2)
+            .and_then(|gap| primes.last().checked_add(gap))
+            .ok_or_else(|| invalid_data("Prime cache gap overflows"))?;
+        primes.push(p);
+    }
+
+    if body.next().is_some() {
+        return Err(invalid_data("Prime cache count mismatch"));
+    }
+    Ok(primes)
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+fn read_varint<'a, I: Iterator<Item = &'a u8>>(bytes: &mut I) -> Option<u64> {
+    let mut n = 0;
+    for shift in (0..64).step_by(7) {
+        let b = *bytes.next()?;
+        n |= ((b & 0x7f) as u64) << shift;
+        if b & 0x80 == 0 {
+            return Some(n);
+        }
+    }
+    None
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET, |hash, &b| {
+        (hash ^ (b as u64)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checksum, write_varint, MAGIC, VERSION};
+    use crate::PrimeSeq;
+    use std::{env, fs, path::PathBuf};
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("prime-cache-{}-{}.bin", std::process::id(), name))
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temp_path("round-trip");
+        let ps = PrimeSeq::new();
+        let expected = ps.iter().take(100_000).collect::<Vec<_>>();
+
+        ps.save(&path).unwrap();
+        let loaded = PrimeSeq::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(expected, loaded.iter().take(100_000).collect::<Vec<_>>());
+        assert_eq!(ps.nth(100_010), loaded.nth(100_010));
+    }
+
+    #[test]
+    fn corrupt() {
+        let path = temp_path("corrupt");
+        let ps = PrimeSeq::new();
+        ps.nth(1000);
+        ps.save(&path).unwrap();
+
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[100] ^= 0x01;
+        fs::write(&path, &bytes).unwrap();
+        assert!(PrimeSeq::load(&path).is_err());
+
+        bytes[0] = b'X';
+        fs::write(&path, &bytes).unwrap();
+        assert!(PrimeSeq::load(&path).is_err());
+
+        fs::write(&path, b"PRMS").unwrap();
+        assert!(PrimeSeq::load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    /// Encode a cache file with the given count and gaps, and a valid checksum.
+    fn encode(count: u64, gaps: &[u64]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend(VERSION.to_le_bytes());
+        bytes.extend(count.to_le_bytes());
+        for &gap in gaps {
+            write_varint(&mut bytes, gap);
+        }
+        let hash = checksum(&bytes);
+        bytes.extend(hash.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn corrupt_with_checksum() {
+        let path = temp_path("corrupt-with-checksum");
+        let load = |bytes: Vec<u8>| {
+            fs::write(&path, bytes).unwrap();
+            PrimeSeq::load(&path).map(|ps| ps.iter().take(4).collect::<Vec<_>>())
+        };
+
+        assert_eq!(vec![2, 3, 5, 7], load(encode(4, &[1, 1])).unwrap());
+        assert!(load(encode(1, &[])).is_err());
+        assert!(load(encode(5, &[1, 1])).is_err());
+        assert!(load(encode(3, &[1, 1])).is_err());
+        assert!(load(encode(4, &[1, u64::MAX >> 1])).is_err());
+        assert!(load(encode(u64::MAX, &[1])).is_err());
+        assert!(load(encode(4, &[1, 0])).is_err());
+        assert!(load(encode(3, &[0])).is_err());
+
+        //  The checksum covers the header, so a corrupt count is caught before the gaps are read.
+        let mut bytes = encode(4, &[1, 1]);
+        bytes[8] = 3;
+        let err = load(bytes).unwrap_err();
+        assert!(err.to_string().contains("checksum"));
+        fs::remove_file(&path).unwrap();
+    }
+}
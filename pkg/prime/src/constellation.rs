@@ -0,0 +1,161 @@
+use crate::{PrimeSeq, PrimeSeqIter};
+use std::mem;
+
+/// Common admissible prime constellation patterns.
+pub mod pattern {
+    /// Twin primes (p, p + 2).
+    pub const TWIN: [u64; 2] = [0, 2];
+    /// Cousin primes (p, p + 4).
+    pub const COUSIN: [u64; 2] = [0, 4];
+    /// Sexy primes (p, p + 6).
+    pub const SEXY: [u64; 2] = [0, 6];
+    /// Prime triplets of the form (p, p + 2, p + 6).
+    pub const TRIPLET_LOW: [u64; 3] = [0, 2, 6];
+    /// Prime triplets of the form (p, p + 4, p + 6).
+    pub const TRIPLET_HIGH: [u64; 3] = [0, 4, 6];
+    /// Prime quadruplets (p, p + 2, p + 6, p + 8).
+    pub const QUADRUPLET: [u64; 4] = [0, 2, 6, 8];
+}
+
+/// An iterator which yields each tuple of primes matching an offset pattern, in ascending order
+/// of the first prime.
+pub struct Constellations<const N: usize> {
+    ps: PrimeSeq,
+    primes: PrimeSeqIter,
+    pattern: [u64; N],
+}
+
+impl<const N: usize> Constellations<N> {
+    pub(crate) fn new(ps: &PrimeSeq, pattern: [u64; N]) -> Self {
+        assert!(N > 0 && pattern[0] == 0, "Pattern must start at offset zero");
+        assert!(
+            pattern.windows(2).all(|w| w[0] < w[1]),
+            "Pattern offsets must be strictly increasing"
+        );
+
+        //  A pattern is admissible if it does not cover every residue modulo any prime, since
+        //  otherwise one of the tuple members is always divisible by that prime. Only primes up
+        //  to the pattern length need to be checked. Inadmissible patterns have finitely many
+        //  matches, so iterating them would never terminate.
+        //
+        for q in ps.iter().take_while(|&q| q <= N as u64) {
+            let mut covered = vec![false; q as usize];
+            pattern
+                .iter()
+                .for_each(|&o| covered[(o % q) as usize] = true);
+            assert!(
+                covered.contains(&false),
+                "Pattern is not admissible, it covers every residue modulo {}",
+                q
+            );
+        }
+
+        Constellations {
+            ps: ps.clone(),
+            primes: ps.iter(),
+            pattern,
+        }
+    }
+}
+
+impl<const N: usize> Iterator for Constellations<N> {
+    type Item = [u64; N];
+
+    fn next(&mut self) -> Option<[u64; N]> {
+        for p in &mut self.primes {
+            if self.pattern[1..].iter().all(|&o| self.ps.contains(p + o)) {
+                return Some(self.pattern.map(|o| p + o));
+            }
+        }
+
+        unreachable!()
+    }
+}
+
+/// An iterator which yields each prime paired with the gap to the next prime.
+pub struct PrimeGaps {
+    primes: PrimeSeqIter,
+    prev: u64,
+}
+
+impl PrimeGaps {
+    pub(crate) fn new(ps: &PrimeSeq) -> Self {
+        let mut primes = ps.iter();
+        let prev = primes.next().unwrap();
+        PrimeGaps { primes, prev }
+    }
+}
+
+impl Iterator for PrimeGaps {
+    type Item = (u64, u64);
+
+    #[inline]
+    fn next(&mut self) -> Option<(u64, u64)> {
+        let p = self.primes.next()?;
+        let prev = mem::replace(&mut self.prev, p);
+        Some((prev, p - prev))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pattern::{COUSIN, QUADRUPLET, SEXY, TRIPLET_HIGH, TRIPLET_LOW, TWIN};
+    use crate::PrimeSeq;
+
+    #[test]
+    fn pairs() {
+        let ps = PrimeSeq::new();
+        assert_eq!(
+            vec![[3, 5], [5, 7], [11, 13], [17, 19], [29, 31], [41, 43]],
+            ps.constellations(TWIN).take(6).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![[3, 7], [7, 11], [13, 17], [19, 23], [37, 41], [43, 47]],
+            ps.constellations(COUSIN).take(6).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![[5, 11], [7, 13], [11, 17], [13, 19], [17, 23], [23, 29]],
+            ps.constellations(SEXY).take(6).collect::<Vec<_>>()
+        );
+        assert_eq!(35, ps.constellations(TWIN).take_while(|t| t[0] < 1000).count());
+    }
+
+    #[test]
+    fn triplets_and_quadruplets() {
+        let ps = PrimeSeq::new();
+        assert_eq!(
+            vec![[5, 7, 11], [11, 13, 17], [17, 19, 23], [41, 43, 47]],
+            ps.constellations(TRIPLET_LOW).take(4).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![[7, 11, 13], [13, 17, 19], [37, 41, 43], [67, 71, 73]],
+            ps.constellations(TRIPLET_HIGH).take(4).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![[5, 7, 11, 13], [11, 13, 17, 19], [101, 103, 107, 109], [191, 193, 197, 199]],
+            ps.constellations(QUADRUPLET).take(4).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn inadmissible() {
+        PrimeSeq::new().constellations([0, 2, 4]);
+    }
+
+    #[test]
+    fn gaps() {
+        let ps = PrimeSeq::new();
+        assert_eq!(
+            vec![(2, 1), (3, 2), (5, 2), (7, 4), (11, 2), (13, 4), (17, 2), (19, 4), (23, 6)],
+            ps.gaps().take(9).collect::<Vec<_>>()
+        );
+
+        let (p, gap) = ps
+            .gaps()
+            .take_while(|&(p, _)| p < 1000)
+            .max_by_key(|&(_, gap)| gap)
+            .unwrap();
+        assert_eq!((887, 20), (p, gap));
+    }
+}
@@ -0,0 +1,120 @@
+use num_integer::Roots;
+use num_traits::Zero;
+use std::ops::{Mul, Sub};
+
+/// Count the number of primes less than or equal to `x`, π(x).
+///
+/// This runs in O(x<sup>3/4</sup>) time and O(x<sup>1/2</sup>) space, and does not require the
+/// primes to be enumerated.
+pub fn prime_pi(x: u64) -> u64 {
+    lucy(x, |v| v - 1, |_| 1)
+}
+
+/// Compute the sum of all primes less than or equal to `x`.
+///
+/// This runs in O(x<sup>3/4</sup>) time and O(x<sup>1/2</sup>) space, and does not require the
+/// primes to be enumerated.
+pub fn prime_sum(x: u64) -> u128 {
+    lucy(x, |v| (v as u128) * (v as u128 + 1) / 2 - 1, |p| p as u128)
+}
+
+/// Evaluate the sum of `g(p)` over all primes `p <= x`, for a completely multiplicative `g`,
+/// using the Lucy_Hedgehog algorithm.
+///
+/// `init(v)` must return the sum of `g(n)` for `2 <= n <= v`, and `weight(p)` must return `g(p)`.
+fn lucy<T, I, W>(x: u64, init: I, weight: W) -> T
+where
+    T: Zero + Copy + Sub<T, Output = T> + Mul<T, Output = T>,
+    I: Fn(u64) -> T,
+    W: Fn(u64) -> T,
+{
+    if x < 2 {
+        return T::zero();
+    }
+
+    //  Let S(v, p) be the sum of g(n) over 2 <= n <= v, where n is either prime or has no prime
+    //  factor less than or equal to p. Only the values v = floor(x / i) are ever required, of
+    //  which there are O(√x). Sieving by each prime p removes the numbers whose smallest prime
+    //  factor is p:
+    //      S(v, p) = S(v, p - 1) - g(p) * (S(v / p, p - 1) - S(p - 1, p - 1))
+    //  After sieving by every p <= √x, S(x) is the sum over the primes.
+    //
+    //  The values v <= r are stored in `small[v]`, and the values v = x / i in `large[i]`.
+    //
+    let r = x.sqrt();
+    let mut small = (0..=r)
+        .map(|v| if v < 2 { T::zero() } else { init(v) })
+        .collect::<Vec<_>>();
+    let mut large = (0..=r)
+        .map(|i| x.checked_div(i).map_or(T::zero(), &init))
+        .collect::<Vec<_>>();
+
+    for p in 2..=r {
+        if (small[p as usize] - small[p as usize - 1]).is_zero() {
+            continue;
+        }
+
+        let sp = small[p as usize - 1];
+        let gp = weight(p);
+        let p2 = p * p;
+
+        for i in 1..=r {
+            let v = x / i;
+            if v < p2 {
+                break;
+            }
+
+            let ip = i * p;
+            let sv = if ip <= r {
+                large[ip as usize]
+            } else {
+                small[(x / ip) as usize]
+            };
+            large[i as usize] = large[i as usize] - gp * (sv - sp);
+        }
+
+        for v in (p2..=r).rev() {
+            let sv = small[(v / p) as usize];
+            small[v as usize] = small[v as usize] - gp * (sv - sp);
+        }
+    }
+
+    large[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{prime_pi, prime_sum};
+    use crate::PrimeSeq;
+
+    #[test]
+    fn prime_pi_small() {
+        let ps = PrimeSeq::new();
+        let mut count = 0;
+        let mut sum = 0;
+        let mut primes = ps.iter().peekable();
+
+        for x in 0..=3000 {
+            while primes.peek().is_some_and(|&p| p <= x) {
+                let p = primes.next().unwrap();
+                count += 1;
+                sum += p as u128;
+            }
+            assert_eq!(count, prime_pi(x));
+            assert_eq!(sum, prime_sum(x));
+        }
+    }
+
+    #[test]
+    fn prime_pi_large() {
+        assert_eq!(78498, prime_pi(1_000_000));
+        assert_eq!(664579, prime_pi(10_000_000));
+        assert_eq!(50847534, prime_pi(1_000_000_000));
+    }
+
+    #[test]
+    fn prime_sum_large() {
+        assert_eq!(142913828922, prime_sum(2_000_000));
+        assert_eq!(24739512092254535, prime_sum(1_000_000_000));
+    }
+}
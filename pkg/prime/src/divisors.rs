@@ -0,0 +1,124 @@
+use crate::Factor;
+use num_integer::Integer;
+use std::vec;
+
+/// An iterator which lazily yields the divisors of a number, generated from its prime factors.
+/// The divisors are not yielded in any particular order.
+pub struct Divisors<T> {
+    /// The multiplier applied when a factor's counter is incremented.
+    steps: Vec<T>,
+    /// The maximum value of each factor's counter.
+    limits: Vec<i32>,
+    /// The current counter of each factor.
+    counters: Vec<i32>,
+    /// The contribution of each factor to the current divisor.
+    powers: Vec<T>,
+    /// The current divisor.
+    current: Option<T>,
+}
+
+impl<T: Integer + Clone> Divisors<T> {
+    /// Enumerate all divisors given the prime factors of a number.
+    pub(crate) fn new<I: Iterator<Item = Factor<T>>>(factors: I) -> Self {
+        let (steps, limits) = factors
+            .map(|factor| (factor.base, factor.exp))
+            .unzip();
+        Self::from_steps(steps, limits)
+    }
+
+    /// Enumerate the unitary divisors given the prime factors of a number. A unitary divisor
+    /// `d` of `n` is a divisor which is coprime to `n / d`.
+    pub(crate) fn unitary<I: Iterator<Item = Factor<T>>>(factors: I) -> Self {
+        let (steps, limits) = factors
+            .map(|factor| (num_traits::pow(factor.base, factor.exp as usize), 1))
+            .unzip();
+        Self::from_steps(steps, limits)
+    }
+
+    /// Construct a divisor iterator which yields nothing, used for the edge case of zero.
+    pub(crate) fn empty() -> Self {
+        Divisors {
+            steps: Vec::new(),
+            limits: Vec::new(),
+            counters: Vec::new(),
+            powers: Vec::new(),
+            current: None,
+        }
+    }
+
+    fn from_steps(steps: Vec<T>, limits: Vec<i32>) -> Self {
+        let len = steps.len();
+        Divisors {
+            steps,
+            limits,
+            counters: vec![0; len],
+            powers: vec![T::one(); len],
+            current: Some(T::one()),
+        }
+    }
+}
+
+impl<T: Integer + Clone> Iterator for Divisors<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        //  The counters form a mixed radix odometer, where each digit is the exponent of a
+        //  prime factor. The current divisor is updated incrementally as the odometer ticks.
+        //
+        let divisor = self.current.take()?;
+        let mut next = divisor.clone();
+        for i in 0..self.steps.len() {
+            if self.counters[i] < self.limits[i] {
+                self.counters[i] += 1;
+                self.powers[i] = self.powers[i].clone() * self.steps[i].clone();
+                next = next * self.steps[i].clone();
+                self.current = Some(next);
+                break;
+            }
+
+            next = next / self.powers[i].clone();
+            self.counters[i] = 0;
+            self.powers[i] = T::one();
+        }
+
+        Some(divisor)
+    }
+}
+
+/// An iterator which yields the pairs of complementary divisors `(d, n / d)` where `d <= n / d`,
+/// in ascending order of `d`.
+pub struct DivisorPairs<T> {
+    divisors: Vec<T>,
+    lo: usize,
+    hi: usize,
+}
+
+impl<T: Integer + Clone> DivisorPairs<T> {
+    pub(crate) fn new(divisors: Divisors<T>) -> Self {
+        let divisors = sorted(divisors).collect::<Vec<_>>();
+        let hi = divisors.len();
+        DivisorPairs { divisors, lo: 0, hi }
+    }
+}
+
+impl<T: Clone> Iterator for DivisorPairs<T> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<(T, T)> {
+        if self.lo >= self.hi {
+            return None;
+        }
+
+        self.hi -= 1;
+        let pair = (self.divisors[self.lo].clone(), self.divisors[self.hi].clone());
+        self.lo += 1;
+        Some(pair)
+    }
+}
+
+/// Collect the divisors and yield them in ascending order.
+pub(crate) fn sorted<T: Integer + Clone>(divisors: Divisors<T>) -> vec::IntoIter<T> {
+    let mut divisors = divisors.collect::<Vec<_>>();
+    divisors.sort_unstable();
+    divisors.into_iter()
+}
@@ -0,0 +1,232 @@
+use crate::{order::multiplicative_order, PrimeSeq};
+use integer::{mod_pow, mul_mod};
+use num_integer::Integer;
+
+/// The expansion of a rational number a / b in a radix, which consists of an integer part, a
+/// finite prefix of fractional digits, and a period of digits which then repeats forever.
+///
+/// The lengths are computed number-theoretically rather than by simulating long division, so
+/// they are cheap even when the period is astronomically long. Writing b = s * m where every
+/// prime factor of s divides the radix and m is coprime to it, the prefix length is the smallest
+/// k such that s divides radix<sup>k</sup>, and the period length is the multiplicative order of
+/// the radix modulo m.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadixExpansion {
+    int: u64,
+    numer: u64,
+    denom: u64,
+    radix: u64,
+    pre_period: usize,
+    period: u64,
+}
+
+impl RadixExpansion {
+    pub(crate) fn new(ps: &PrimeSeq, numer: u64, denom: u64, radix: u64) -> Self {
+        assert!(denom > 0, "Denominator must be non-zero");
+        assert!(radix >= 2, "Radix must be at least two");
+
+        let (int, numer) = numer.div_rem(&denom);
+        let gcd = numer.gcd(&denom);
+        let (numer, denom) = (numer / gcd, denom / gcd);
+
+        //  Each step divides out one power of every prime shared with the radix, so the number
+        //  of steps is the largest ceil(v_p(b) / v_p(radix)), which is exactly the prefix length.
+        //
+        let mut coprime = denom;
+        let mut pre_period = 0;
+        loop {
+            let g = coprime.gcd(&radix);
+            if g == 1 {
+                break;
+            }
+            coprime /= g;
+            pre_period += 1;
+        }
+
+        let period = match coprime {
+            1 => 0,
+            m => multiplicative_order(ps, radix % m, m).unwrap(),
+        };
+        RadixExpansion {
+            int,
+            numer,
+            denom,
+            radix,
+            pre_period,
+            period,
+        }
+    }
+
+    /// Get the integer part of the number.
+    #[inline]
+    pub fn integer_part(&self) -> u64 {
+        self.int
+    }
+
+    /// Evaluate if the expansion terminates, meaning it has no repeating period.
+    #[inline]
+    pub fn is_terminating(&self) -> bool {
+        self.period == 0
+    }
+
+    /// Get the number of fractional digits before the period begins.
+    #[inline]
+    pub fn pre_period_len(&self) -> usize {
+        self.pre_period
+    }
+
+    /// Get the length of the repeating period, which is zero for terminating expansions.
+    #[inline]
+    pub fn period_len(&self) -> u64 {
+        self.period
+    }
+
+    /// Get the fractional digits before the period begins. For a terminating expansion these are
+    /// all of the fractional digits.
+    pub fn prefix(&self) -> Vec<u64> {
+        self.digits().take(self.pre_period).collect()
+    }
+
+    /// Get the digits of one repetition of the period. This takes time linear in the period
+    /// length, which may be very large.
+    pub fn period_digits(&self) -> Vec<u64> {
+        let rem = mul_mod(
+            self.numer,
+            mod_pow(self.radix, self.pre_period as u64, self.denom),
+            self.denom,
+        );
+        ExpansionDigits::new(rem, self.denom, self.radix)
+            .take(self.period as usize)
+            .collect()
+    }
+
+    /// Get the k<sup>th</sup> fractional digit, counting from one, without generating the digits
+    /// before it.
+    pub fn nth_digit(&self, k: u64) -> u64 {
+        assert!(k > 0, "Fractional digits are counted from one");
+        let rem = mul_mod(
+            self.numer,
+            mod_pow(self.radix, k - 1, self.denom),
+            self.denom,
+        );
+        ((rem as u128 * self.radix as u128) / self.denom as u128) as u64
+    }
+
+    /// Create a lazy iterator over the fractional digits, which ends if the expansion terminates.
+    #[inline]
+    pub fn digits(&self) -> ExpansionDigits {
+        ExpansionDigits::new(self.numer, self.denom, self.radix)
+    }
+}
+
+/// An iterator which yields the fractional digits of a rational number by long division.
+#[derive(Clone)]
+pub struct ExpansionDigits {
+    rem: u64,
+    denom: u64,
+    radix: u64,
+}
+
+impl ExpansionDigits {
+    fn new(rem: u64, denom: u64, radix: u64) -> Self {
+        ExpansionDigits { rem, denom, radix }
+    }
+}
+
+impl Iterator for ExpansionDigits {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        if self.rem == 0 {
+            return None;
+        }
+
+        let (digit, rem) = (self.rem as u128 * self.radix as u128).div_rem(&(self.denom as u128));
+        self.rem = rem as u64;
+        Some(digit as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PrimeSeq;
+    use std::collections::HashMap;
+
+    const RADIX: u64 = 10;
+
+    /// Find the prefix and period lengths by simulating long division.
+    fn simulate(numer: u64, denom: u64, radix: u64) -> (usize, u64) {
+        let mut seen = HashMap::new();
+        let mut rem = numer % denom;
+        let mut pos = 0;
+        while rem != 0 {
+            if let Some(start) = seen.insert(rem, pos) {
+                return (start, (pos - start) as u64);
+            }
+            rem = rem * radix % denom;
+            pos += 1;
+        }
+        (pos, 0)
+    }
+
+    #[test]
+    fn shape() {
+        let ps = PrimeSeq::new();
+        for radix in 2..=16 {
+            for denom in 1..300 {
+                for numer in [1, 2, 7, denom + 3] {
+                    let expansion = ps.radix_expansion(numer, denom, radix);
+                    let (pre, period) = simulate(numer, denom, radix);
+                    assert_eq!(pre, expansion.pre_period_len());
+                    assert_eq!(period, expansion.period_len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn digits() {
+        let ps = PrimeSeq::new();
+        let seventh = ps.radix_expansion(1, 7, RADIX);
+        assert_eq!(Vec::<u64>::new(), seventh.prefix());
+        assert_eq!(vec![1, 4, 2, 8, 5, 7], seventh.period_digits());
+
+        let twelfth = ps.radix_expansion(1, 12, RADIX);
+        assert_eq!(vec![0, 8], twelfth.prefix());
+        assert_eq!(vec![3], twelfth.period_digits());
+
+        let terminating = ps.radix_expansion(123, 40, RADIX);
+        assert!(terminating.is_terminating());
+        assert_eq!(3, terminating.integer_part());
+        assert_eq!(vec![0, 7, 5], terminating.prefix());
+        assert_eq!(vec![0, 7, 5], terminating.digits().collect::<Vec<_>>());
+        assert!(terminating.period_digits().is_empty());
+
+        let third = ps.radix_expansion(1, 3, 2);
+        assert_eq!(vec![0, 1, 0, 1], third.digits().take(4).collect::<Vec<_>>());
+        assert_eq!(982, ps.radix_expansion(1, 983, RADIX).period_len());
+    }
+
+    #[test]
+    fn large_denominator() {
+        let ps = PrimeSeq::new();
+        let p = 1_000_000_007;
+        let expansion = ps.radix_expansion(1, p, RADIX);
+        assert_eq!(0, expansion.pre_period_len());
+        assert_eq!(0, (p - 1) % expansion.period_len());
+
+        let digits = expansion.digits().take(50).collect::<Vec<_>>();
+        for (i, &d) in digits.iter().enumerate() {
+            assert_eq!(d, expansion.nth_digit(i as u64 + 1));
+        }
+        assert_eq!(
+            expansion.nth_digit(1),
+            expansion.nth_digit(expansion.period_len() + 1)
+        );
+
+        let expansion = ps.radix_expansion(5, 1 << 40, RADIX);
+        assert!(expansion.is_terminating());
+        assert_eq!(40, expansion.pre_period_len());
+    }
+}
@@ -1,16 +1,53 @@
+use num_bigint::{BigUint, ToBigUint};
 use num_integer::Integer;
-use num_traits::{FromPrimitive, One, Zero};
+use num_traits::{CheckedMul, FromPrimitive, One, Zero};
+use store::PrimeStore;
 use std::{
     cell::RefCell,
-    collections::{
-        hash_map::Entry::{Occupied, Vacant},
-        HashMap,
-    },
-    hash::Hash,
+    cmp,
+    collections::{btree_map, BTreeMap},
+    fmt, fs,
+    io,
+    iter::Rev,
     mem,
+    ops::Range,
+    path::Path,
     rc::Rc,
+    vec,
 };
 
+mod binomial;
+mod cache;
+mod constellation;
+mod count;
+mod divisors;
+mod expansion;
+mod multiplicative;
+mod order;
+mod residue;
+mod segment;
+mod sieve;
+mod spf;
+mod store;
+
+pub use binomial::BinomialMod;
+pub use constellation::{pattern, Constellations, PrimeGaps};
+pub use count::{prime_pi, prime_sum};
+pub use divisors::{DivisorPairs, Divisors};
+pub use expansion::{ExpansionDigits, RadixExpansion};
+pub use multiplicative::{du_sieve, hyperbola_sum, mertens, totient_sum, Multiplicative, Ring};
+pub use residue::{
+    cipolla, jacobi_symbol, legendre_symbol, sqrt_mod_prime, sqrt_mod_prime_power, tonelli_shanks,
+};
+pub use segment::PrimeRange;
+pub use sieve::{
+    divisor_count_sieve, divisor_sum_sieve, mobius_sieve, multiplicative_sieve, totient_sieve,
+};
+pub use spf::{CompactSpfSieve, Factorizations, SieveWord, SpfFactorsIter, SpfSieve};
+
+/// The default location of the on-disk prime cache, alongside the solver input files.
+pub const DEFAULT_CACHE_PATH: &str = "cache/primes.bin";
+
 const INITIAL_CAPACITY: usize = 10000;
 const SEED_PRIMES: &[u64] = &[
     2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
@@ -43,6 +80,45 @@ impl PrimeSeq {
         Self::from_inner(PrimeInner::seeded(capacity))
     }
 
+    /// Get an iterator which yields each tuple of primes `(p + o1, p + o2, ...)` matching the
+    /// offset pattern, e.g. `[0, 2]` for twin primes. See the `pattern` module for common
+    /// constellations. Panics if the pattern is not admissible.
+    pub fn constellations<const N: usize>(&self, pattern: [u64; N]) -> Constellations<N> {
+        Constellations::new(self, pattern)
+    }
+
+    /// Get an iterator which yields each prime paired with the gap to the next prime.
+    pub fn gaps(&self) -> PrimeGaps {
+        PrimeGaps::new(self)
+    }
+
+    /// Load a prime number generator from a cache file previously written by `save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let computed = cache::read(path)?;
+        Ok(Self::from_inner(PrimeInner { computed }))
+    }
+
+    /// Construct a prime number generator containing at least `len` primes, loaded from the
+    /// default cache file when possible. If the cache is missing, corrupt, or too short, the
+    /// primes are computed and the cache is rewritten. Failure to write the cache is ignored.
+    pub fn cached(len: usize) -> Self {
+        let path = Path::new(DEFAULT_CACHE_PATH);
+        let ps = Self::load(path).unwrap_or_else(|_| Self::seeded(len));
+        if ps.data.borrow().computed.len() < len {
+            ps.nth(len - 1);
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let _ = ps.save(path);
+        }
+        ps
+    }
+
+    /// Save all primes computed so far to a compact binary cache file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        cache::write(&self.data.borrow().computed, path)
+    }
+
     /// Compute the n<sup>th</sup> prime, where n is zero-indexed.
     #[inline]
     pub fn nth(&self, n: usize) -> u64 {
@@ -63,8 +139,94 @@ impl PrimeSeq {
         }
     }
 
+    /// Get an iterator which yields the primes within the range in ascending order. Only the
+    /// primes up to the square root of the end of the range are computed and cached, the range
+    /// itself is sieved in segments.
+    pub fn range(&self, range: Range<u64>) -> PrimeRange {
+        PrimeRange::new(self.clone(), range.start, range.end)
+    }
+
+    /// Get an iterator which yields the primes greater than or equal to `n` in ascending order.
+    pub fn iter_from(&self, n: u64) -> PrimeRange {
+        PrimeRange::new(self.clone(), n, u64::MAX)
+    }
+
+    /// Get an iterator which yields the primes less than or equal to `n` in descending order.
+    pub fn rev_from(&self, n: u64) -> Rev<PrimeRange> {
+        PrimeRange::new(self.clone(), 0, n.saturating_add(1)).rev()
+    }
+
     /// Calculate the combination nCr.
     pub fn combinations(&self, n: u64, k: u64) -> u64 {
+        self.factorized_combinations(n, k).into_integer()
+    }
+
+    /// Calculate the combination nCr as an arbitrary precision integer.
+    pub fn combinations_big(&self, n: u64, k: u64) -> BigUint {
+        self.factorized_combinations(n, k).into_biguint().unwrap()
+    }
+
+    /// Calculate the combination nCr modulo `m`. Prime moduli use Lucas' theorem, and composite
+    /// moduli are split into prime powers which are recombined with the Chinese remainder theorem.
+    /// No tables are kept between calls, so use `BinomialMod` for many queries against one prime.
+    /// Each repeated prime factor p<sup>e</sup> of `m` costs O(p<sup>e</sup> log n) time.
+    pub fn combinations_mod(&self, n: u64, k: u64, m: u64) -> u64 {
+        binomial::combinations_mod(self, n, k, m)
+    }
+
+    /// Calculate the permutation nPr.
+    pub fn permutations(&self, n: u64, k: u64) -> u64 {
+        self.factorized_permutations(n, k).into_integer()
+    }
+
+    /// Calculate the permutation nPr as an arbitrary precision integer.
+    pub fn permutations_big(&self, n: u64, k: u64) -> BigUint {
+        self.factorized_permutations(n, k).into_biguint().unwrap()
+    }
+
+    /// Calculate the permutation nPr modulo `m`.
+    pub fn permutations_mod(&self, n: u64, k: u64, m: u64) -> u64 {
+        binomial::permutations_mod(n, k, m)
+    }
+
+    /// Calculate the multiplicative order of `a` modulo `n`, the smallest `k > 0` such that
+    /// a<sup>k</sup> ≡ 1 (mod n), or `None` if `a` is not coprime to `n`.
+    pub fn multiplicative_order(&self, a: u64, n: u64) -> Option<u64> {
+        order::multiplicative_order(self, a, n)
+    }
+
+    /// Find the smallest primitive root modulo `n`, or `None` if there is no primitive root.
+    pub fn primitive_root(&self, n: u64) -> Option<u64> {
+        order::primitive_root(self, n)
+    }
+
+    /// Evaluate if `g` is a primitive root modulo `n`.
+    pub fn is_primitive_root(&self, g: u64, n: u64) -> bool {
+        order::is_primitive_root(self, g, n)
+    }
+
+    /// Find the smallest `x` such that g<sup>x</sup> ≡ h (mod n), or `None` if there is no
+    /// solution. Uses Pohlig–Hellman over the order of `g`, with baby-step giant-step for each
+    /// prime factor of the order. A base sharing factors with `n` is first reduced to a coprime
+    /// one by dividing out the common factors.
+    pub fn discrete_log(&self, g: u64, h: u64, n: u64) -> Option<u64> {
+        order::discrete_log(self, g, h, n)
+    }
+
+    /// Find all square roots of `a` modulo `n`, in ascending order. The roots modulo each prime
+    /// power factor of `n` are found by lifting a root modulo the prime, then combined using the
+    /// Chinese remainder theorem.
+    pub fn sqrt_mod(&self, a: u64, n: u64) -> Vec<u64> {
+        residue::sqrt_mod(self, a, n)
+    }
+
+    /// Analyze the expansion of `numer / denom` in the radix, whose period length is the
+    /// multiplicative order of the radix modulo the part of `denom` coprime to it.
+    pub fn radix_expansion(&self, numer: u64, denom: u64, radix: u64) -> RadixExpansion {
+        RadixExpansion::new(self, numer, denom, radix)
+    }
+
+    fn factorized_combinations(&self, n: u64, k: u64) -> Factorized<u64> {
         assert!(n >= k);
         let mut factorized = Factorized::<u64>::new(self);
         for i in (n - k + 1)..=n {
@@ -73,11 +235,10 @@ impl PrimeSeq {
         for i in 1..=k {
             factorized.div(i);
         }
-        factorized.into_integer()
+        factorized
     }
 
-    /// Calculate the permutation nPr.
-    pub fn permutations(&self, n: u64, k: u64) -> u64 {
+    fn factorized_permutations(&self, n: u64, k: u64) -> Factorized<u64> {
         assert!(n >= k);
         let mut factorized = Factorized::<u64>::new(self);
         for i in 1..=n {
@@ -86,7 +247,7 @@ impl PrimeSeq {
         for i in 1..=(n - k) {
             factorized.div(i);
         }
-        factorized.into_integer()
+        factorized
     }
 
     #[inline]
@@ -121,7 +282,7 @@ impl Iterator for PrimeSeqIter {
 }
 
 /// A factor of a number, specifying the base and exponent of the factor.
-#[derive(Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct Factor<T> {
     pub base: T,
     pub exp: i32,
@@ -188,6 +349,167 @@ pub trait Factorize: Integer + FromPrimitive + Clone {
         }
         self.sum_divisors(ps) - self.clone()
     }
+
+    /// An iterator yielding all divisors of the number, in no particular order.
+    fn divisors(&self, ps: &PrimeSeq) -> Divisors<Self> {
+        if self.is_zero() {
+            return Divisors::empty();
+        }
+        Divisors::new(self.factorize(ps))
+    }
+
+    /// An iterator yielding all divisors of the number in ascending order.
+    fn sorted_divisors(&self, ps: &PrimeSeq) -> vec::IntoIter<Self> {
+        divisors::sorted(self.divisors(ps))
+    }
+
+    /// An iterator yielding all proper divisors of the number in ascending order. The proper
+    /// divisors are all divisors other than the number itself.
+    fn proper_divisors(&self, ps: &PrimeSeq) -> vec::IntoIter<Self> {
+        let mut divisors = self.sorted_divisors(ps).collect::<Vec<_>>();
+        divisors.pop();
+        divisors.into_iter()
+    }
+
+    /// An iterator yielding all unitary divisors of the number, in no particular order. A
+    /// unitary divisor `d` is a divisor which is coprime to `n / d`.
+    fn unitary_divisors(&self, ps: &PrimeSeq) -> Divisors<Self> {
+        if self.is_zero() {
+            return Divisors::empty();
+        }
+        Divisors::unitary(self.factorize(ps))
+    }
+
+    /// An iterator yielding the pairs of complementary divisors `(d, n / d)` where `d <= n / d`,
+    /// in ascending order of `d`.
+    fn divisor_pairs(&self, ps: &PrimeSeq) -> DivisorPairs<Self> {
+        DivisorPairs::new(self.divisors(ps))
+    }
+
+    /// Compute the sum of the k<sup>th</sup> powers of the divisors of the number.
+    fn sigma_k(&self, ps: &PrimeSeq, k: u32) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        //  This is a generalization of the geometric progression used by `sum_divisors`, where
+        //  each prime factor contributes:
+        //      1 + p^k + p^2k + ... = (p^(k(e + 1)) - 1)/(p^k - 1)
+        //  For k = 0 each prime factor simply contributes (e + 1).
+        //
+        let one: Self = Self::one();
+        self.factorize(ps)
+            .map(|factor| {
+                if k == 0 {
+                    return Self::from_i32(factor.exp + 1).unwrap();
+                }
+                let pk = num_traits::pow(factor.base, k as usize);
+                let den = pk.clone() - one.clone();
+                let num = num_traits::pow(pk, (factor.exp as usize) + 1) - one.clone();
+                num/den
+            })
+            .fold(Self::one(), |acc, n| acc * n)
+    }
+
+    /// Compute Euler's totient φ(n), the number of positive integers up to n which are coprime
+    /// to n.
+    fn totient(&self, ps: &PrimeSeq) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        //  φ(N) = p1^(e1 - 1)(p1 - 1) * p2^(e2 - 1)(p2 - 1) * ...
+        //
+        let one: Self = Self::one();
+        self.factorize(ps)
+            .map(|factor| {
+                let pm = factor.base.clone() - one.clone();
+                num_traits::pow(factor.base, (factor.exp as usize) - 1) * pm
+            })
+            .fold(Self::one(), |acc, n| acc * n)
+    }
+
+    /// Compute the Möbius function μ(n), which is `0` if the number has a squared prime factor,
+    /// and otherwise `-1` or `1` for an odd or even number of prime factors respectively.
+    fn mobius(&self, ps: &PrimeSeq) -> i32 {
+        if self.is_zero() {
+            return 0;
+        }
+
+        let mut mu = 1;
+        for factor in self.factorize(ps) {
+            if factor.exp > 1 {
+                return 0;
+            }
+            mu = -mu;
+        }
+        mu
+    }
+
+    /// Compute the Liouville function λ(n) = (-1)<sup>Ω(n)</sup>.
+    fn liouville(&self, ps: &PrimeSeq) -> i32 {
+        if self.is_zero() {
+            return 0;
+        }
+
+        if self.big_omega(ps).is_even() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Compute the Carmichael function λ(n), the smallest positive integer m such that
+    /// a<sup>m</sup> ≡ 1 (mod n) for every integer a coprime to n.
+    fn carmichael_lambda(&self, ps: &PrimeSeq) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        //  λ(N) is the least common multiple of λ(p^e) over the prime power factors of N, where
+        //  λ(p^e) = φ(p^e) except for powers of two above 4, for which λ(2^e) = 2^(e - 2).
+        //
+        let one: Self = Self::one();
+        let two: Self = one.clone() + one.clone();
+        self.factorize(ps)
+            .map(|factor| {
+                if factor.base == two && factor.exp >= 3 {
+                    num_traits::pow(two.clone(), (factor.exp as usize) - 2)
+                } else {
+                    let pm = factor.base.clone() - one.clone();
+                    num_traits::pow(factor.base, (factor.exp as usize) - 1) * pm
+                }
+            })
+            .fold(Self::one(), |acc, n| acc.lcm(&n))
+    }
+
+    /// Compute the radical of the number, which is the product of its distinct prime factors.
+    fn radical(&self, ps: &PrimeSeq) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        self.factorize(ps)
+            .fold(Self::one(), |acc, factor| acc * factor.base)
+    }
+
+    /// Compute ω(n), the number of distinct prime factors of the number.
+    fn omega(&self, ps: &PrimeSeq) -> u32 {
+        if self.is_zero() {
+            return 0;
+        }
+        self.factorize(ps).count() as u32
+    }
+
+    /// Compute Ω(n), the number of prime factors of the number counted with multiplicity.
+    fn big_omega(&self, ps: &PrimeSeq) -> u32 {
+        if self.is_zero() {
+            return 0;
+        }
+        self.factorize(ps)
+            .map(|factor| factor.exp as u32)
+            .sum()
+    }
 }
 
 /// Implement the Factorize trait for an unsigned integer type.
@@ -253,79 +575,207 @@ impl<T: Integer + FromPrimitive + Clone> Iterator for FactorsIter<T> {
 }
 
 /// A factorized number that provides multiply and divide methods which try to avoid overflow.
-pub struct Factorized<'a, T> {
-    ps: &'a PrimeSeq,
-    factors: HashMap<T, i32>,
+/// The prime factors are stored in ascending order, and may have negative exponents.
+#[derive(Clone)]
+pub struct Factorized<T> {
+    ps: PrimeSeq,
+    factors: BTreeMap<T, i32>,
 }
 
-impl<T: Factorize + Eq + Hash> Factorized<'_, T> {
+impl<T: Factorize> Factorized<T> {
     /// Create a new factorized number representing the integer `1`.
-    pub fn new(ps: &PrimeSeq) -> Factorized<'_, T> {
+    pub fn new(ps: &PrimeSeq) -> Factorized<T> {
         Factorized {
-            ps,
-            factors: HashMap::new(),
+            ps: ps.clone(),
+            factors: BTreeMap::new(),
         }
     }
 
     /// Create a factorized number from an integer type.
-    pub fn from_integer(ps: &PrimeSeq, n: T) -> Factorized<'_, T> {
+    pub fn from_integer(ps: &PrimeSeq, n: T) -> Factorized<T> {
         Factorized {
-            ps,
+            ps: ps.clone(),
             factors: n.factorize(ps).map(|f| (f.base, f.exp)).collect(),
         }
     }
 
     /// Convert the factorized number into an integer type.
-    pub fn into_integer(self) -> T {
+    ///
+    /// Panics if the number is not an integer, or if the integer type would overflow.
+    pub fn into_integer(self) -> T
+    where
+        T: CheckedMul,
+    {
+        self.checked_into_integer()
+            .expect("Factorized number cannot be represented by the integer type")
+    }
+
+    /// Convert the factorized number into an integer type, returning `None` if the number is not
+    /// an integer or if the integer type would overflow.
+    pub fn checked_into_integer(self) -> Option<T>
+    where
+        T: CheckedMul,
+    {
+        if !self.is_integer() {
+            return None;
+        }
+
         self.factors
             .into_iter()
-            .fold::<T, _>(One::one(), |prod, (base, exp)| {
-                if exp >= 0 {
-                    prod * num_traits::pow(base, exp as usize)
-                } else {
-                    prod / num_traits::pow(base, (-exp) as usize)
-                }
+            .try_fold(T::one(), |prod, (base, exp)| {
+                prod.checked_mul(&num_traits::checked_pow(base, exp as usize)?)
+            })
+    }
+
+    /// Convert the factorized number into an arbitrary precision integer, returning `None` if the
+    /// number is not an integer.
+    pub fn into_biguint(self) -> Option<BigUint>
+    where
+        T: ToBigUint,
+    {
+        if !self.is_integer() {
+            return None;
+        }
+
+        self.factors
+            .into_iter()
+            .try_fold(BigUint::one(), |prod, (base, exp)| {
+                Some(prod * base.to_biguint()?.pow(exp as u32))
             })
     }
 
+    /// Returns `true` if the factorized number is an integer, i.e. no exponent is negative.
+    pub fn is_integer(&self) -> bool {
+        self.factors.values().all(|&exp| exp >= 0)
+    }
+
+    /// Get an iterator which yields the prime factors in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Factor<T>> + '_ {
+        self.factors
+            .iter()
+            .map(|(base, &exp)| Factor { base: base.clone(), exp })
+    }
+
     /// Multiplies the given number into the factorized number.
     pub fn mul(&mut self, n: T) {
-        for factor in n.factorize(self.ps) {
-            match self.factors.entry(factor.base) {
-                Vacant(entry) => {
-                    entry.insert(factor.exp);
-                }
-                Occupied(entry) => {
-                    *entry.into_mut() += factor.exp;
-                }
-            }
+        for factor in n.factorize(&self.ps) {
+            self.add_exp(factor.base, factor.exp);
         }
     }
 
     /// Divides the factorized number by the given number.
     pub fn div(&mut self, n: T) {
-        for factor in n.factorize(self.ps) {
-            match self.factors.entry(factor.base) {
-                Vacant(entry) => {
-                    entry.insert(-factor.exp);
-                }
-                Occupied(entry) => {
-                    *entry.into_mut() -= factor.exp;
+        for factor in n.factorize(&self.ps) {
+            self.add_exp(factor.base, -factor.exp);
+        }
+    }
+
+    /// Compute the greatest common divisor of two factorized numbers, which takes the minimum
+    /// exponent of each prime factor.
+    pub fn gcd(&self, other: &Factorized<T>) -> Factorized<T> {
+        self.combine(other, cmp::min)
+    }
+
+    /// Compute the least common multiple of two factorized numbers, which takes the maximum
+    /// exponent of each prime factor.
+    pub fn lcm(&self, other: &Factorized<T>) -> Factorized<T> {
+        self.combine(other, cmp::max)
+    }
+
+    /// Raise the factorized number to the given power.
+    pub fn pow(&self, exp: i32) -> Factorized<T> {
+        if exp == 0 {
+            return Factorized::new(&self.ps);
+        }
+
+        Factorized {
+            ps: self.ps.clone(),
+            factors: self
+                .factors
+                .iter()
+                .map(|(base, &e)| (base.clone(), e * exp))
+                .collect(),
+        }
+    }
+
+    fn add_exp(&mut self, base: T, exp: i32) {
+        match self.factors.entry(base) {
+            btree_map::Entry::Vacant(entry) => {
+                entry.insert(exp);
+            }
+            btree_map::Entry::Occupied(mut entry) => {
+                *entry.get_mut() += exp;
+                if *entry.get() == 0 {
+                    entry.remove();
                 }
             }
         }
     }
+
+    fn combine<F: Fn(i32, i32) -> i32>(&self, other: &Factorized<T>, f: F) -> Factorized<T> {
+        let factors = self
+            .factors
+            .keys()
+            .chain(other.factors.keys())
+            .map(|base| {
+                let a = self.factors.get(base).copied().unwrap_or(0);
+                let b = other.factors.get(base).copied().unwrap_or(0);
+                (base.clone(), f(a, b))
+            })
+            .filter(|&(_, exp)| exp != 0)
+            .collect();
+
+        Factorized {
+            ps: self.ps.clone(),
+            factors,
+        }
+    }
+}
+
+impl<T: Eq> PartialEq for Factorized<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.factors == other.factors
+    }
+}
+
+impl<T: Eq> Eq for Factorized<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for Factorized<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.factors.iter()).finish()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Factorized<T> {
+    /// Formats the number as a product of prime powers, e.g. `2^3 · 3 · 7`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.factors.is_empty() {
+            return write!(f, "1");
+        }
+
+        for (i, (base, &exp)) in self.factors.iter().enumerate() {
+            if i > 0 {
+                write!(f, " · ")?;
+            }
+            if exp == 1 {
+                write!(f, "{}", base)?;
+            } else {
+                write!(f, "{}^{}", base, exp)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 /// A struct used to cache computed primes.
 struct PrimeInner {
-    computed: Vec<u64>,
+    computed: PrimeStore,
 }
 
 impl PrimeInner {
     #[inline]
     fn new() -> Self {
-        let mut computed = Vec::with_capacity(INITIAL_CAPACITY);
+        let mut computed = PrimeStore::with_capacity(INITIAL_CAPACITY);
         computed.push(SEED_PRIMES[0]);
         computed.push(SEED_PRIMES[1]);
         PrimeInner { computed }
@@ -333,26 +783,26 @@ impl PrimeInner {
 
     #[inline]
     fn seeded(capacity: usize) -> Self {
-        let mut computed = Vec::with_capacity(capacity + SEED_PRIMES.len());
-        computed.extend(SEED_PRIMES.iter());
+        let mut computed = PrimeStore::with_capacity(capacity + SEED_PRIMES.len());
+        computed.extend(SEED_PRIMES.iter().copied());
         PrimeInner { computed }
     }
 
     #[inline]
     fn max_computed(&self) -> u64 {
-        *self.computed.last().unwrap()
+        self.computed.last()
     }
 
     #[inline]
     fn compute_nth(&mut self, n: usize) -> u64 {
         self.grow_to_len(n + 1);
-        self.computed[n]
+        self.computed.get(n)
     }
 
     #[inline]
     fn check_prime(&mut self, n: u64) -> bool {
         if n <= self.max_computed() {
-            return self.computed.binary_search(&n).is_ok();
+            return self.computed.contains(n);
         }
 
         if !self.is_coprime_to_all_computed(n) {
@@ -369,8 +819,8 @@ impl PrimeInner {
     fn is_coprime_to_all_computed(&self, n: u64) -> bool {
         self.computed
             .iter()
-            .take_while(|&&p| p * p <= n)
-            .all(|&p| !Integer::is_multiple_of(&n, &p))
+            .take_while(|&p| p * p <= n)
+            .all(|p| !Integer::is_multiple_of(&n, &p))
     }
 
     #[inline]
@@ -393,7 +843,8 @@ impl PrimeInner {
 
 #[cfg(test)]
 mod tests {
-    use super::{Factorize, PrimeSeq, SEED_PRIMES};
+    use super::{Factor, Factorize, Factorized, PrimeSeq, SEED_PRIMES};
+    use num_bigint::BigUint;
 
     #[test]
     fn prime_seq_iter() {
@@ -563,6 +1014,202 @@ mod tests {
         }
     }
 
+    #[test]
+    fn divisors() {
+        let ps = PrimeSeq::new();
+        for n in 0..=500u64 {
+            let expected = (1..=n).filter(|d| n % d == 0).collect::<Vec<_>>();
+            let mut unsorted = n.divisors(&ps).collect::<Vec<_>>();
+            unsorted.sort();
+
+            assert_eq!(expected, unsorted);
+            assert_eq!(expected, n.sorted_divisors(&ps).collect::<Vec<_>>());
+            assert_eq!(n.num_divisors(&ps), n.divisors(&ps).count() as u64);
+        }
+
+        assert_eq!(vec![1, 2, 3, 6], (-6).sorted_divisors(&ps).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn proper_divisors() {
+        let ps = PrimeSeq::new();
+        assert_eq!(Vec::<u32>::new(), 0u32.proper_divisors(&ps).collect::<Vec<_>>());
+        assert_eq!(Vec::<u32>::new(), 1u32.proper_divisors(&ps).collect::<Vec<_>>());
+        assert_eq!(vec![1], 13u32.proper_divisors(&ps).collect::<Vec<_>>());
+        assert_eq!(vec![1, 2, 4, 7, 14], 28u32.proper_divisors(&ps).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn unitary_divisors() {
+        let pairs: &[(u32, &[u32])] = &[
+            (1, &[1]),
+            (12, &[1, 3, 4, 12]),
+            (60, &[1, 3, 4, 5, 12, 15, 20, 60]),
+            (72, &[1, 8, 9, 72]),
+        ];
+
+        let ps = PrimeSeq::new();
+        for &(n, expected) in pairs {
+            let mut actual = n.unitary_divisors(&ps).collect::<Vec<_>>();
+            actual.sort();
+            assert_eq!(expected, &actual[..]);
+        }
+    }
+
+    #[test]
+    fn divisor_pairs() {
+        let ps = PrimeSeq::new();
+        assert_eq!(
+            vec![(1, 36), (2, 18), (3, 12), (4, 9), (6, 6)],
+            36u32.divisor_pairs(&ps).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(1, 12), (2, 6), (3, 4)],
+            12u32.divisor_pairs(&ps).collect::<Vec<_>>()
+        );
+        assert_eq!(0, 0u32.divisor_pairs(&ps).count());
+    }
+
+    #[test]
+    fn sigma_k() {
+        let triples = &[
+            (0, 2, 0),      // Edge case
+            (1, 2, 1),      // {1} -> 1
+            (2, 2, 5),      // {1, 4} -> 5
+            (6, 2, 50),     // {1, 4, 9, 36} -> 50
+            (10, 3, 1134),  // {1, 8, 125, 1000} -> 1134
+            (12, 0, 6),     // Equivalent to the number of divisors
+            (12, 1, 28),    // Equivalent to the sum of divisors
+            (12, 2, 210),   // {1, 4, 9, 16, 36, 144} -> 210
+        ];
+
+        let ps = PrimeSeq::new();
+        for &(n, k, sigma) in triples {
+            assert_eq!(sigma, n.sigma_k(&ps, k));
+        }
+    }
+
+    #[test]
+    fn totient() {
+        let pairs = &[
+            (0, 0),     // Edge case
+            (1, 1),     // {1}
+            (2, 1),     // {1}
+            (6, 2),     // {1, 5}
+            (9, 6),     // {1, 2, 4, 5, 7, 8}
+            (10, 4),    // {1, 3, 7, 9}
+            (12, 4),    // {1, 5, 7, 11}
+            (36, 12),
+            (97, 96),
+            (100, 40),
+        ];
+
+        let ps = PrimeSeq::new();
+        for &(n, phi) in pairs {
+            assert_eq!(phi, n.totient(&ps));
+        }
+    }
+
+    #[test]
+    fn mobius_and_liouville() {
+        let triples = &[
+            (1, 1, 1),
+            (2, -1, -1),
+            (4, 0, 1),
+            (6, 1, 1),
+            (8, 0, -1),
+            (12, 0, -1),
+            (30, -1, -1),
+            (36, 0, 1),
+            (210, 1, 1),
+        ];
+
+        let ps = PrimeSeq::new();
+        for &(n, mu, lambda) in triples {
+            assert_eq!(mu, n.mobius(&ps));
+            assert_eq!(lambda, n.liouville(&ps));
+        }
+    }
+
+    #[test]
+    fn carmichael_lambda() {
+        let pairs = &[
+            (0, 0),     // Edge case
+            (1, 1),
+            (2, 1),
+            (4, 2),
+            (8, 2),
+            (16, 4),
+            (15, 4),
+            (24, 2),
+            (561, 80),  // Smallest Carmichael number
+            (100, 20),
+        ];
+
+        let ps = PrimeSeq::new();
+        for &(n, lambda) in pairs {
+            assert_eq!(lambda, n.carmichael_lambda(&ps));
+        }
+    }
+
+    #[test]
+    fn radical_and_omega() {
+        let quads = &[
+            (0, 0, 0, 0),   // Edge case
+            (1, 1, 0, 0),
+            (8, 2, 1, 3),
+            (12, 6, 2, 3),
+            (30, 30, 3, 3),
+            (360, 30, 3, 6),
+            (644, 322, 3, 4),
+        ];
+
+        let ps = PrimeSeq::new();
+        for &(n, rad, omega, big_omega) in quads {
+            assert_eq!(rad, n.radical(&ps));
+            assert_eq!(omega, n.omega(&ps));
+            assert_eq!(big_omega, n.big_omega(&ps));
+        }
+    }
+
+    #[test]
+    fn factorized() {
+        let ps = PrimeSeq::new();
+        let a = Factorized::from_integer(&ps, 168u64);
+        let b = Factorized::from_integer(&ps, 180u64);
+
+        assert_eq!("2^3 · 3 · 7", a.to_string());
+        assert_eq!("2^2 · 3^2 · 5", b.to_string());
+        assert_eq!("1", Factorized::<u64>::new(&ps).to_string());
+        assert_eq!(Some(12), a.gcd(&b).checked_into_integer());
+        assert_eq!(Some(2520), a.lcm(&b).checked_into_integer());
+        assert_eq!(Some(168 * 168 * 168), a.pow(3).checked_into_integer());
+        assert_eq!(Factorized::new(&ps), a.pow(0));
+        assert_eq!(
+            vec![Factor { base: 2, exp: 3 }, Factor { base: 3, exp: 1 }, Factor { base: 7, exp: 1 }],
+            a.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn factorized_checked() {
+        let ps = PrimeSeq::new();
+        let mut f = Factorized::from_integer(&ps, 12u32);
+        f.div(8);
+        assert!(!f.is_integer());
+        assert_eq!("2^-1 · 3", f.to_string());
+        assert_eq!(None, f.clone().checked_into_integer());
+        assert_eq!(None, f.clone().into_biguint());
+
+        f.mul(2);
+        assert!(f.is_integer());
+        assert_eq!(Some(3), f.checked_into_integer());
+
+        let big = Factorized::from_integer(&ps, 1u32 << 31).pow(2);
+        assert_eq!(None, big.clone().checked_into_integer());
+        assert_eq!(Some(BigUint::from(1u64 << 62)), big.into_biguint());
+    }
+
     #[test]
     fn combination() {
         let ps = PrimeSeq::new();
@@ -572,6 +1219,16 @@ mod tests {
         assert_eq!(ps.combinations(18, 4), 3060);
     }
 
+    #[test]
+    fn combination_big() {
+        let ps = PrimeSeq::new();
+        assert_eq!(ps.combinations_big(5, 3), BigUint::from(10u32));
+        assert_eq!(
+            ps.combinations_big(100, 50),
+            "100891344545564193334812497256".parse::<BigUint>().unwrap()
+        );
+    }
+
     #[test]
     fn permutation() {
         let ps = PrimeSeq::new();
@@ -580,4 +1237,14 @@ mod tests {
         assert_eq!(ps.permutations(8, 3), 336);
         assert_eq!(ps.permutations(10, 4), 5040);
     }
+
+    #[test]
+    fn permutation_big() {
+        let ps = PrimeSeq::new();
+        assert_eq!(ps.permutations_big(10, 4), BigUint::from(5040u32));
+        assert_eq!(
+            ps.permutations_big(30, 30),
+            "265252859812191058636308480000000".parse::<BigUint>().unwrap()
+        );
+    }
 }
@@ -0,0 +1,303 @@
+use crate::{mobius_sieve, multiplicative_sieve, totient_sieve, Factorize, PrimeSeq};
+use num_integer::Roots;
+use num_traits::{One, Zero};
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+use std::rc::Rc;
+
+/// The arithmetic operations required of the values of an arithmetic function.
+pub trait Ring:
+    Clone + Zero + One + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+}
+
+impl<T> Ring for T where T: Clone + Zero + One + Add<Output = T> + Sub<Output = T> + Mul<Output = T> {}
+
+/// A multiplicative arithmetic function, defined by its values on prime powers.
+///
+/// `f(p, e)` is the value of the function at p<sup>e</sup> for `e >= 1`, and the value at `1`
+/// is always one. Functions can be combined using Dirichlet convolution, and inverted.
+#[derive(Clone)]
+pub struct Multiplicative<T> {
+    f: Rc<dyn Fn(u64, u32) -> T>,
+}
+
+impl<T: Ring + 'static> Multiplicative<T> {
+    /// Create a multiplicative function from its values on prime powers.
+    pub fn new<F: Fn(u64, u32) -> T + 'static>(f: F) -> Self {
+        Multiplicative { f: Rc::new(f) }
+    }
+
+    /// The Dirichlet identity ε, which is one at `1` and zero elsewhere.
+    pub fn identity() -> Self {
+        Multiplicative::new(|_, _| T::zero())
+    }
+
+    /// The constant function 1.
+    pub fn unit() -> Self {
+        Multiplicative::new(|_, _| T::one())
+    }
+
+    /// The Möbius function μ, the Dirichlet inverse of 1.
+    pub fn mobius() -> Self {
+        Multiplicative::new(|_, e| {
+            if e == 1 {
+                T::zero() - T::one()
+            } else {
+                T::zero()
+            }
+        })
+    }
+
+    /// Evaluate the function at a prime power p<sup>e</sup>.
+    #[inline]
+    pub fn at_prime_power(&self, p: u64, e: u32) -> T {
+        if e == 0 {
+            T::one()
+        } else {
+            (self.f)(p, e)
+        }
+    }
+
+    /// Evaluate the function at `n`, by factorizing it.
+    pub fn eval(&self, ps: &PrimeSeq, n: u64) -> T {
+        assert!(n > 0);
+        n.factorize(ps)
+            .map(|factor| (self.f)(factor.base, factor.exp as u32))
+            .fold(T::one(), |acc, v| acc * v)
+    }
+
+    /// Tabulate the function for every integer in `[0, limit]`, where the entry for `0` is zero.
+    pub fn values(&self, limit: usize) -> Vec<T> {
+        multiplicative_sieve(limit, |p, e| (self.f)(p, e))
+    }
+
+    /// Tabulate the summatory function, the sum of the function over `[1, n]`, for every `n` in
+    /// `[0, limit]`.
+    pub fn prefix_sums(&self, limit: usize) -> Vec<T> {
+        prefix_sums(self.values(limit).into_iter())
+    }
+
+    /// Compute the Dirichlet convolution (f * g)(n), the sum of f(d) g(n / d) over the divisors
+    /// `d` of `n`, which is also multiplicative.
+    pub fn convolve(&self, other: &Multiplicative<T>) -> Multiplicative<T> {
+        let (f, g) = (self.clone(), other.clone());
+        Multiplicative::new(move |p, e| {
+            (0..=e).fold(T::zero(), |acc, i| {
+                acc + f.at_prime_power(p, i) * g.at_prime_power(p, e - i)
+            })
+        })
+    }
+
+    /// Compute the Dirichlet inverse, the function g such that f * g = ε.
+    pub fn inverse(&self) -> Multiplicative<T> {
+        //  Comparing the coefficients of p^e in f * g = ε gives, for e >= 1,
+        //      g(p^e) = -(f(p) g(p^(e-1)) + f(p^2) g(p^(e-2)) + ... + f(p^e))
+        //
+        let f = self.clone();
+        Multiplicative::new(move |p, e| {
+            let mut g = vec![T::one()];
+            for k in 1..=e {
+                let sum = (1..=k).fold(T::zero(), |acc, i| {
+                    acc + f.at_prime_power(p, i) * g[(k - i) as usize].clone()
+                });
+                g.push(T::zero() - sum);
+            }
+            g.pop().unwrap()
+        })
+    }
+}
+
+impl<T> fmt::Debug for Multiplicative<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Multiplicative")
+    }
+}
+
+/// Sum the Dirichlet convolution (f * g)(n) over `[1, x]` using the Dirichlet hyperbola method,
+/// in O(√x) evaluations.
+///
+/// `f` and `g` evaluate the functions, and `sum_f` and `sum_g` their summatory functions.
+pub fn hyperbola_sum<T, F, SF, G, SG>(x: u64, f: F, sum_f: SF, g: G, sum_g: SG) -> T
+where
+    T: Ring,
+    F: Fn(u64) -> T,
+    SF: Fn(u64) -> T,
+    G: Fn(u64) -> T,
+    SG: Fn(u64) -> T,
+{
+    //  Each pair (d, e) with d * e <= x has d <= √x or e <= √x, so
+    //      Σ (f * g)(n) = Σ_{d <= √x} f(d) G(x / d) + Σ_{e <= √x} g(e) F(x / e) - F(√x) G(√x)
+    //  where the last term removes the pairs counted twice.
+    //
+    let r = x.sqrt();
+    let total = (1..=r).fold(T::zero(), |acc, d| {
+        acc + f(d) * sum_g(x / d) + g(d) * sum_f(x / d)
+    });
+    total - sum_f(r) * sum_g(r)
+}
+
+/// Compute the summatory function S(x) of a function f using Du's sieve, in
+/// O(x<sup>2/3</sup>) time when `small` covers the values up to x<sup>2/3</sup>.
+///
+/// This requires a function g with g(1) = 1 such that the summatory functions of g and f * g,
+/// `sum_g` and `sum_h`, are cheap to evaluate. `small[n]` must hold S(n) for each `n` it covers,
+/// and must cover at least up to √x.
+pub fn du_sieve<T, SG, SH>(x: u64, small: &[T], sum_g: SG, sum_h: SH) -> T
+where
+    T: Ring,
+    SG: Fn(u64) -> T,
+    SH: Fn(u64) -> T,
+{
+    let len = small.len() as u64;
+    if x < len {
+        return small[x as usize].clone();
+    }
+    assert!(len > x.sqrt(), "Table must cover the values up to √x");
+
+    //  Summing (f * g)(n) over n <= v and grouping by the cofactor gives
+    //      H(v) = Σ_{d <= v} g(d) S(v / d)
+    //  so S(v) = H(v) - Σ_{2 <= d <= v} g(d) S(v / d), where the sum is grouped into blocks
+    //  of d with equal v / d. Only the values v = x / i are needed, and those not covered by
+    //  the table are stored in `large[i]` and computed in ascending order of v.
+    //
+    let max_i = x / len;
+    let mut large = vec![T::zero(); max_i as usize + 1];
+    for i in (1..=max_i).rev() {
+        let v = x / i;
+        let mut sum = T::zero();
+        let mut d = 2;
+        while d <= v {
+            let q = v / d;
+            let next = v / q;
+            let s = if q < len {
+                small[q as usize].clone()
+            } else {
+                large[(i * d) as usize].clone()
+            };
+            sum = sum + (sum_g(next) - sum_g(d - 1)) * s;
+            d = next + 1;
+        }
+        large[i as usize] = sum_h(v) - sum;
+    }
+
+    large[1].clone()
+}
+
+/// Compute the Mertens function M(x), the sum of the Möbius function over `[1, x]`, in
+/// O(x<sup>2/3</sup>) time.
+pub fn mertens(x: u64) -> i64 {
+    let small = prefix_sums(mobius_sieve(sieve_limit(x)).into_iter().map(i64::from));
+    du_sieve(x, &small, |v| v as i64, |v| (v >= 1) as i64)
+}
+
+/// Compute the sum of Euler's totient φ(n) over `[1, x]`, in O(x<sup>2/3</sup>) time.
+pub fn totient_sum(x: u64) -> u128 {
+    let small = prefix_sums(totient_sieve(sieve_limit(x)).into_iter().map(u128::from));
+    du_sieve(
+        x,
+        &small,
+        |v| v as u128,
+        |v| v as u128 * (v as u128 + 1) / 2,
+    )
+}
+
+/// The table size for Du's sieve, around x<sup>2/3</sup>.
+fn sieve_limit(x: u64) -> usize {
+    (x.cbrt().pow(2) as usize).max(x.sqrt() as usize + 1)
+}
+
+fn prefix_sums<T: Ring, I: Iterator<Item = T>>(values: I) -> Vec<T> {
+    values
+        .scan(T::zero(), |acc, v| {
+            *acc = acc.clone() + v;
+            Some(acc.clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{du_sieve, hyperbola_sum, mertens, totient_sum, Multiplicative};
+    use crate::{mobius_sieve, totient_sieve, Factorize, PrimeSeq};
+
+    #[test]
+    fn convolution_and_inverse() {
+        let ps = PrimeSeq::new();
+        let unit = Multiplicative::<i64>::unit();
+        let mobius = Multiplicative::<i64>::mobius();
+        let id = Multiplicative::<i64>::new(|p, e| p.pow(e) as i64);
+        let totient = Multiplicative::<i64>::new(|p, e| (p.pow(e - 1) * (p - 1)) as i64);
+
+        let identity = mobius.convolve(&unit);
+        let divisor_count = unit.convolve(&unit);
+        let divisor_sum = id.convolve(&unit);
+        let totient_unit = totient.convolve(&unit);
+        let inverse = unit.inverse();
+        let double_inverse = totient.inverse().inverse();
+
+        for n in 1..=500u64 {
+            assert_eq!((n == 1) as i64, identity.eval(&ps, n));
+            assert_eq!(
+                (n == 1) as i64,
+                Multiplicative::<i64>::identity().eval(&ps, n)
+            );
+            assert_eq!(n.num_divisors(&ps) as i64, divisor_count.eval(&ps, n));
+            assert_eq!(n.sum_divisors(&ps) as i64, divisor_sum.eval(&ps, n));
+            assert_eq!(n as i64, totient_unit.eval(&ps, n));
+            assert_eq!(n.mobius(&ps) as i64, inverse.eval(&ps, n));
+            assert_eq!(totient.eval(&ps, n), double_inverse.eval(&ps, n));
+        }
+
+        let values = divisor_sum.values(100);
+        let sums = divisor_sum.prefix_sums(100);
+        assert_eq!(0, values[0]);
+        assert_eq!(217, values[100]);
+        assert_eq!(8299, sums[100]);
+    }
+
+    #[test]
+    fn hyperbola() {
+        let one = |_| 1u64;
+        let count = |x| x;
+        assert_eq!(482, hyperbola_sum(100, one, count, one, count));
+        assert_eq!(
+            8299,
+            hyperbola_sum(100, |d| d, |x| x * (x + 1) / 2, one, count)
+        );
+
+        let tau = Multiplicative::<u64>::unit().convolve(&Multiplicative::unit());
+        let sums = tau.prefix_sums(5000);
+        for x in 0..=5000 {
+            assert_eq!(sums[x as usize], hyperbola_sum(x, one, count, one, count));
+        }
+    }
+
+    #[test]
+    fn du_sieve_sums() {
+        let limit = 20_000;
+        let mu = mobius_sieve(limit);
+        let phi = totient_sieve(limit);
+        let (mut m, mut s) = (0i64, 0u128);
+        for x in 1..=limit {
+            m += mu[x] as i64;
+            s += phi[x] as u128;
+            assert_eq!(m, mertens(x as u64));
+            assert_eq!(s, totient_sum(x as u64));
+        }
+
+        assert_eq!(212, mertens(1_000_000));
+        assert_eq!(-222, mertens(1_000_000_000));
+        assert_eq!(303_963_552_392, totient_sum(1_000_000));
+
+        //  The divisor count τ satisfies τ * μ = 1, so it can be summed using the Mertens function
+        let tau = Multiplicative::<i64>::unit().convolve(&Multiplicative::unit());
+        let sums = tau.prefix_sums(1000);
+        let mobius_sums = Multiplicative::<i64>::mobius().prefix_sums(1000);
+        assert_eq!(7069, sums[1000]);
+        assert_eq!(
+            sums[1000],
+            du_sieve(1000, &sums[..40], |v| mobius_sums[v as usize], |v| v as i64)
+        );
+    }
+}
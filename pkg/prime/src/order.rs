@@ -0,0 +1,228 @@
+use crate::{Factorize, PrimeSeq};
+use integer::{crt, mod_inv, mod_pow, mul_mod};
+use num_integer::{Integer, Roots};
+use std::collections::HashMap;
+
+/// Compute the multiplicative order of `a` modulo `n`, the smallest `k > 0` such that
+/// a<sup>k</sup> ≡ 1 (mod n). Returns `None` if `a` is not coprime to `n`.
+pub(crate) fn multiplicative_order(ps: &PrimeSeq, a: u64, n: u64) -> Option<u64> {
+    assert!(n > 0, "Modulus must be positive");
+    if a.gcd(&n) != 1 {
+        return None;
+    }
+
+    //  The order divides the Carmichael function λ(n), so starting from λ(n) each prime factor
+    //  is divided out for as long as the reduced exponent still maps `a` to one.
+    //
+    let lambda = n.carmichael_lambda(ps);
+    let mut order = lambda;
+    for factor in lambda.factorize(ps) {
+        for _ in 0..factor.exp {
+            if mod_pow(a, order / factor.base, n) != 1 % n {
+                break;
+            }
+            order /= factor.base;
+        }
+    }
+    Some(order)
+}
+
+/// Evaluate if `g` is a primitive root modulo `n`, meaning `g` generates the multiplicative
+/// group of integers modulo `n`.
+pub(crate) fn is_primitive_root(ps: &PrimeSeq, g: u64, n: u64) -> bool {
+    multiplicative_order(ps, g, n) == Some(n.totient(ps))
+}
+
+/// Find the smallest primitive root modulo `n`, or `None` if the multiplicative group modulo `n`
+/// is not cyclic. Primitive roots exist only for 1, 2, 4, p<sup>k</sup> and 2p<sup>k</sup>,
+/// where p is an odd prime.
+pub(crate) fn primitive_root(ps: &PrimeSeq, n: u64) -> Option<u64> {
+    assert!(n > 0, "Modulus must be positive");
+    if n <= 4 {
+        return Some(if n <= 2 { 1 } else { n - 1 });
+    }
+
+    let odd = if n % 4 == 2 { n / 2 } else { n };
+    if odd.is_even() || odd.factorize(ps).count() != 1 {
+        return None;
+    }
+
+    let phi = n.totient(ps);
+    let factors = phi.factorize(ps).map(|f| f.base).collect::<Vec<_>>();
+    (2..n).find(|&g| g.gcd(&n) == 1 && factors.iter().all(|&q| mod_pow(g, phi / q, n) != 1))
+}
+
+/// Solve the discrete logarithm problem g<sup>x</sup> ≡ h (mod n) for the smallest `x >= 0`, or
+/// return `None` if no solution exists. The base need not be coprime to `n`.
+pub(crate) fn discrete_log(ps: &PrimeSeq, g: u64, h: u64, n: u64) -> Option<u64> {
+    assert!(n > 0, "Modulus must be positive");
+    let (g, mut h, mut n) = (g % n, h % n, n);
+
+    //  While d = gcd(g, n) > 1, any solution x > 0 of k * g^x ≡ h (mod n) also solves
+    //      k * (g / d) * g^(x - 1) ≡ h / d (mod n / d)
+    //  and there is none if d does not divide h. Each step divides n by at least two, so after
+    //  at most log2(n) steps the base is coprime to the modulus, and the exponents skipped on
+    //  the way are checked directly.
+    //
+    let mut k = 1 % n;
+    let mut offset = 0;
+    loop {
+        let d = g.gcd(&n);
+        if d == 1 {
+            break;
+        }
+        if h == k {
+            return Some(offset);
+        }
+        if h % d != 0 {
+            return None;
+        }
+        n /= d;
+        h /= d;
+        k = mul_mod(k, g / d, n);
+        offset += 1;
+    }
+
+    let h = mul_mod(h, mod_inv(k, n)?, n);
+    discrete_log_coprime(ps, g % n, h, n).map(|x| x + offset)
+}
+
+/// Solve g<sup>x</sup> ≡ h (mod n) for the smallest `x >= 0`, where `g` is coprime to `n`.
+fn discrete_log_coprime(ps: &PrimeSeq, g: u64, h: u64, n: u64) -> Option<u64> {
+    let order = multiplicative_order(ps, g, n)?;
+
+    //  Pohlig–Hellman reduces the problem to the prime power factors q^e of the order of g. For
+    //  each, x mod q^e is recovered one base q digit at a time, where each digit is a discrete
+    //  logarithm in the subgroup of order q, solved with baby-step giant-step. The residues are
+    //  then combined using the Chinese remainder theorem.
+    //
+    let mut congruences = Vec::new();
+    for factor in order.factorize(ps) {
+        let (q, e) = (factor.base, factor.exp as u32);
+        let qe = q.pow(e);
+        let gamma = mod_pow(g, order / q, n);
+        let g_i = mod_pow(g, order / qe, n);
+        let h_i = mod_pow(h, order / qe, n);
+        let g_i_inv = mod_inv(g_i, n)?;
+
+        let mut x = 0;
+        let mut q_k = 1;
+        for k in 0..e {
+            let eta = mul_mod(h_i, mod_pow(g_i_inv, x, n), n);
+            let eta = mod_pow(eta, qe / q_k / q, n);
+            let d = baby_step_giant_step(gamma, eta, q, n)?;
+            x += d * q_k;
+            if k + 1 < e {
+                q_k *= q;
+            }
+        }
+        congruences.push((x, qe));
+    }
+
+    let (x, _) = crt(&congruences)?;
+    Some(x).filter(|&x| mod_pow(g, x, n) == h)
+}
+
+/// Solve g<sup>x</sup> ≡ h (mod n) for `0 <= x < order`, using O(√order) time and memory.
+fn baby_step_giant_step(g: u64, h: u64, order: u64, n: u64) -> Option<u64> {
+    let m = (order - 1).sqrt() + 1;
+    let mut baby = HashMap::with_capacity(m as usize);
+    let mut gj = 1 % n;
+    for j in 0..m {
+        baby.entry(gj).or_insert(j);
+        gj = mul_mod(gj, g, n);
+    }
+
+    let giant = mod_inv(mod_pow(g, m, n), n)?;
+    let mut gamma = h;
+    for i in 0..m {
+        if let Some(&j) = baby.get(&gamma) {
+            return Some(i * m + j);
+        }
+        gamma = mul_mod(gamma, giant, n);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PrimeSeq;
+    use integer::mod_pow;
+
+    #[test]
+    fn multiplicative_order() {
+        let ps = PrimeSeq::new();
+        assert_eq!(Some(1), ps.multiplicative_order(1, 7));
+        assert_eq!(Some(6), ps.multiplicative_order(3, 7));
+        assert_eq!(Some(3), ps.multiplicative_order(2, 7));
+        assert_eq!(Some(6), ps.multiplicative_order(10, 7));
+        assert_eq!(Some(982), ps.multiplicative_order(10, 983));
+        assert_eq!(Some(2), ps.multiplicative_order(3, 8));
+        assert_eq!(None, ps.multiplicative_order(4, 8));
+
+        for n in 1..200u64 {
+            for a in 1..n {
+                let expected = (1..=n).find(|&k| mod_pow(a, k, n) == 1 % n);
+                if num_integer::gcd(a, n) == 1 {
+                    assert_eq!(expected, ps.multiplicative_order(a, n));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn primitive_root() {
+        let ps = PrimeSeq::new();
+        let pairs = &[
+            (1, Some(1)),
+            (2, Some(1)),
+            (3, Some(2)),
+            (4, Some(3)),
+            (7, Some(3)),
+            (8, None),
+            (9, Some(2)),
+            (12, None),
+            (18, Some(5)),
+            (23, Some(5)),
+            (41, Some(6)),
+            (50, Some(3)),
+            (1_000_000_007, Some(5)),
+        ];
+
+        for &(n, g) in pairs {
+            assert_eq!(g, ps.primitive_root(n));
+            if let Some(g) = g {
+                assert!(ps.is_primitive_root(g, n));
+            }
+        }
+
+        assert!(!ps.is_primitive_root(2, 7));
+    }
+
+    #[test]
+    fn discrete_log() {
+        let ps = PrimeSeq::new();
+        assert_eq!(Some(4), ps.discrete_log(3, 13, 17));
+        assert_eq!(Some(0), ps.discrete_log(2, 1, 11));
+        assert_eq!(None, ps.discrete_log(2, 3, 7));
+        assert_eq!(Some(10), ps.discrete_log(3, 59049 % 1000, 1000));
+        assert_eq!(Some(2), ps.discrete_log(2, 4, 8));
+        assert_eq!(Some(3), ps.discrete_log(2, 0, 8));
+        assert_eq!(None, ps.discrete_log(2, 3, 8));
+        assert_eq!(Some(1), ps.discrete_log(0, 0, 5));
+        assert_eq!(Some(12), ps.discrete_log(6, 6u64.pow(12) % 1_000_000, 1_000_000));
+
+        let p = 1_000_000_007;
+        let x = 123_456_789;
+        assert_eq!(Some(x), ps.discrete_log(5, mod_pow(5, x, p), p));
+
+        for n in 1..60u64 {
+            for g in 0..n {
+                for h in 0..n {
+                    let expected = (0..=n).find(|&y| mod_pow(g, y, n) == h);
+                    assert_eq!(expected, ps.discrete_log(g, h, n));
+                }
+            }
+        }
+    }
+}
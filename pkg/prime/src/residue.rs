@@ -0,0 +1,361 @@
+use crate::{Factorize, PrimeSeq};
+use integer::{add_mod, crt, mod_inv, mod_pow, mul_mod, sub_mod};
+
+/// Calculate the Legendre symbol (a/p) for an odd prime `p`, which is 0 if `p` divides `a`, 1 if
+/// `a` is a quadratic residue modulo `p`, and -1 otherwise.
+pub fn legendre_symbol(a: i64, p: u64) -> i32 {
+    assert!(p > 2 && p % 2 == 1, "Modulus must be an odd prime");
+    legendre(reduce(a, p), p)
+}
+
+/// Reduce a signed `a` modulo `m`, without wrapping for moduli above 2<sup>63</sup>.
+#[inline]
+fn reduce(a: i64, m: u64) -> u64 {
+    (a as i128).rem_euclid(m as i128) as u64
+}
+
+/// Calculate the Legendre symbol (a/p) of a reduced `a`.
+fn legendre(a: u64, p: u64) -> i32 {
+    match mod_pow(a, (p - 1) / 2, p) {
+        0 => 0,
+        1 => 1,
+        _ => -1,
+    }
+}
+
+/// Calculate the Jacobi symbol (a/n) for an odd positive `n`, the product of the Legendre symbols
+/// over the prime factors of `n`. It is -1 only if `a` is a quadratic non-residue modulo `n`, but
+/// a value of 1 does not guarantee a residue when `n` is composite.
+pub fn jacobi_symbol(a: i64, n: u64) -> i32 {
+    assert!(n % 2 == 1, "Modulus must be odd and positive");
+
+    //  This uses quadratic reciprocity to swap arguments, and the supplementary law
+    //  (2/n) = (-1)^((n^2 - 1) / 8) to remove factors of two, without factorizing n.
+    //
+    let mut a = reduce(a, n);
+    let mut n = n;
+    let mut result = 1;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Find a square root of `a` modulo the prime `p`, or `None` if `a` is a non-residue. The root
+/// returned is the smaller of the two, and the other is `p` minus it.
+pub fn sqrt_mod_prime(a: u64, p: u64) -> Option<u64> {
+    //  Tonelli–Shanks takes O(s^2) multiplications where 2^s is the largest power of two dividing
+    //  p - 1, so for primes with a large power of two Cipolla's O(log p) algorithm is faster.
+    //
+    if (p - 1).trailing_zeros() <= 8 {
+        tonelli_shanks(a, p)
+    } else {
+        cipolla(a, p)
+    }
+}
+
+/// Find a square root of `a` modulo the prime `p` using the Tonelli–Shanks algorithm, or `None`
+/// if `a` is a non-residue. The root returned is the smaller of the two.
+pub fn tonelli_shanks(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    if legendre(a, p) != 1 {
+        return None;
+    }
+
+    //  Write p - 1 = q * 2^s with q odd. Starting from r = a^((q + 1) / 2), t = a^q, the error
+    //  t is pushed into ever smaller power of two subgroups using powers of a non-residue z.
+    //
+    let s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let z = (2..p)
+        .find(|&z| legendre(z, p) == -1)
+        .unwrap();
+
+    let mut m = s;
+    let mut c = mod_pow(z, q, p);
+    let mut t = mod_pow(a, q, p);
+    let mut r = mod_pow(a, q.div_ceil(2), p);
+    while t != 1 {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != 1 {
+            t2 = mul_mod(t2, t2, p);
+            i += 1;
+        }
+
+        let b = mod_pow(c, 1 << (m - i - 1), p);
+        m = i;
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        r = mul_mod(r, b, p);
+    }
+
+    Some(r.min(p - r))
+}
+
+/// Find a square root of `a` modulo the odd prime `p` using Cipolla's algorithm, or `None` if
+/// `a` is a non-residue. The root returned is the smaller of the two.
+pub fn cipolla(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if a == 0 || p == 2 {
+        return Some(a);
+    }
+    if legendre(a, p) != 1 {
+        return None;
+    }
+
+    //  Find t such that w = t^2 - a is a non-residue, then work in the field extension
+    //  F_p(√w), where (t + √w)^((p + 1) / 2) is a square root of a lying in F_p.
+    //
+    let (t, w) = (0..p)
+        .map(|t| (t, sub_mod(mul_mod(t, t, p), a, p)))
+        .find(|&(_, w)| legendre(w, p) == -1)
+        .unwrap();
+
+    let mul = |(x1, y1): (u64, u64), (x2, y2): (u64, u64)| {
+        let x = add_mod(mul_mod(x1, x2, p), mul_mod(mul_mod(y1, y2, p), w, p), p);
+        let y = add_mod(mul_mod(x1, y2, p), mul_mod(y1, x2, p), p);
+        (x, y)
+    };
+
+    let mut result = (1, 0);
+    let mut base = (t, 1);
+    let mut exp = p.div_ceil(2);
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = mul(result, base);
+        }
+        base = mul(base, base);
+        exp /= 2;
+    }
+
+    let r = result.0;
+    Some(r.min(p - r))
+}
+
+/// Find all square roots of `a` modulo the prime power p<sup>e</sup>, in ascending order.
+pub fn sqrt_mod_prime_power(a: u64, p: u64, e: u32) -> Vec<u64> {
+    assert!(e > 0, "Exponent must be positive");
+    let pe = p.pow(e);
+    let a = a % pe;
+
+    //  If a = p^v * b with b coprime to p, then v must be even for a root to exist, and every
+    //  root has the form x = p^(v/2) * y where y^2 ≡ b (mod p^(e - v)). Since x is only needed
+    //  modulo p^e, each such y yields p^(v/2) distinct roots. When p^e divides a, the roots are
+    //  the multiples of p^ceil(e/2).
+    //
+    if a == 0 {
+        let step = p.pow(e.div_ceil(2));
+        return (0..pe).step_by(step as usize).collect();
+    }
+
+    let mut v = 0;
+    let mut b = a;
+    while b.is_multiple_of(p) {
+        b /= p;
+        v += 1;
+    }
+    if v % 2 == 1 {
+        return vec![];
+    }
+
+    let (half, rest) = (v / 2, e - v);
+    let unit_roots = sqrt_mod_prime_power_unit(b, p, rest);
+    let step = p.pow(rest);
+    let scale = p.pow(half);
+    let mut roots = unit_roots
+        .iter()
+        .flat_map(|&y| (0..scale).map(move |k| (y + k * step) * scale))
+        .collect::<Vec<_>>();
+    roots.sort_unstable();
+    roots
+}
+
+/// Find all square roots of `a` modulo `n`, in ascending order. The modulus is split into prime
+/// powers, and the roots modulo each are combined using the Chinese remainder theorem.
+pub(crate) fn sqrt_mod(ps: &PrimeSeq, a: u64, n: u64) -> Vec<u64> {
+    assert!(n > 0, "Modulus must be positive");
+    let mut roots = vec![(0, 1)];
+    for factor in n.factorize(ps) {
+        let exp = factor.exp as u32;
+        let pe = factor.base.pow(exp);
+        let local = sqrt_mod_prime_power(a, factor.base, exp);
+        roots = roots
+            .iter()
+            .flat_map(|&r| local.iter().map(move |&s| crt(&[r, (s, pe)]).unwrap()))
+            .collect();
+    }
+
+    let mut roots = roots.into_iter().map(|(r, _)| r).collect::<Vec<_>>();
+    roots.sort_unstable();
+    roots
+}
+
+/// Find all square roots of `a` modulo p<sup>e</sup>, where `a` is coprime to `p`.
+fn sqrt_mod_prime_power_unit(a: u64, p: u64, e: u32) -> Vec<u64> {
+    let pe = p.pow(e);
+    if p == 2 {
+        //  Odd squares are 1 modulo 8, and modulo 2^e for e >= 3 there are exactly four roots
+        //  ±r and ±r + 2^(e-1). A root modulo 2^k is lifted to 2^(k+1) by adding 2^(k-1) when
+        //  it is not already a root.
+        //
+        return match e {
+            1 => vec![1],
+            2 if a % 4 == 1 => vec![1, 3],
+            2 => vec![],
+            _ if a % 8 != 1 => vec![],
+            _ => {
+                let mut r = 1u64;
+                for k in 3..e {
+                    if mul_mod(r, r, 1 << (k + 1)) != a % (1 << (k + 1)) {
+                        r += 1 << (k - 1);
+                    }
+                }
+                let half = pe / 2;
+                let mut roots = vec![r, pe - r, (r + half) % pe, (pe - r + half) % pe];
+                roots.sort_unstable();
+                roots
+            }
+        };
+    }
+
+    //  Hensel's lemma lifts a root r modulo p^k to p^(k+1), since 2r is invertible:
+    //      r' = r - (r^2 - a) / (2r)
+    //
+    let Some(mut r) = sqrt_mod_prime(a % p, p) else {
+        return vec![];
+    };
+    let mut pk = p;
+    for _ in 1..e {
+        pk *= p;
+        let f = sub_mod(mul_mod(r, r, pk), a % pk, pk);
+        let inv = mod_inv(2 * r % pk, pk).unwrap();
+        r = sub_mod(r, mul_mod(f, inv, pk), pk);
+    }
+
+    let mut roots = vec![r, pe - r];
+    roots.sort_unstable();
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cipolla, jacobi_symbol, legendre_symbol, sqrt_mod_prime_power, tonelli_shanks};
+    use crate::{Factorize, PrimeSeq};
+    use integer::mul_mod;
+
+    fn brute_force(a: u64, n: u64) -> Vec<u64> {
+        (0..n).filter(|&x| x * x % n == a % n).collect()
+    }
+
+    #[test]
+    fn symbols() {
+        let ps = PrimeSeq::new();
+        for p in ps.iter().skip(1).take_while(|&p| p < 100) {
+            for a in -50..50i64 {
+                let expected = match a.rem_euclid(p as i64) {
+                    0 => 0,
+                    r if brute_force(r as u64, p).is_empty() => -1,
+                    _ => 1,
+                };
+                assert_eq!(expected, legendre_symbol(a, p));
+            }
+        }
+
+        for n in (1..200u64).step_by(2) {
+            for a in -20..20i64 {
+                let expected = n
+                    .factorize(&ps)
+                    .map(|f| legendre_symbol(a, f.base).pow(f.exp as u32))
+                    .product::<i32>();
+                assert_eq!(expected, jacobi_symbol(a, n));
+            }
+        }
+
+        assert_eq!(-1, jacobi_symbol(1001, 9907));
+        assert_eq!(1, jacobi_symbol(19, 45));
+
+        let p = 18_446_744_073_709_551_557;
+        assert_eq!(1, legendre_symbol(-1, p));
+        assert_eq!(-1, legendre_symbol(2, p));
+        assert_eq!(-1, legendre_symbol(i64::MAX, p));
+        assert_eq!(-1, jacobi_symbol(-3, p));
+        assert_eq!(-1, jacobi_symbol(i64::MIN, p));
+    }
+
+    #[test]
+    fn sqrt_mod_prime() {
+        let ps = PrimeSeq::new();
+        for p in ps.iter().take_while(|&p| p < 200) {
+            for a in 0..p {
+                let expected = brute_force(a, p).first().copied();
+                assert_eq!(expected, tonelli_shanks(a, p));
+                assert_eq!(expected, cipolla(a, p));
+                assert_eq!(expected, super::sqrt_mod_prime(a, p));
+            }
+        }
+
+        let p = 998_244_353;
+        let a = 123_456_789 * 123_456_789 % p;
+        let r = super::sqrt_mod_prime(a, p).unwrap();
+        assert_eq!(a, r * r % p);
+        assert_eq!(Some(r), tonelli_shanks(a, p));
+        assert_eq!(None, super::sqrt_mod_prime(3, p));
+
+        //  Moduli above 2^63 must not wrap when reduced or when adding in the field extension.
+        let p = 18_446_744_073_709_551_557;
+        let a = mul_mod(u64::MAX - 12, u64::MAX - 12, p);
+        let r = tonelli_shanks(a, p).unwrap();
+        assert_eq!(a, mul_mod(r, r, p));
+        assert_eq!(Some(r), cipolla(a, p));
+    }
+
+    #[test]
+    fn sqrt_mod_prime_powers() {
+        for (p, e) in [
+            (2, 1),
+            (2, 2),
+            (2, 3),
+            (2, 6),
+            (3, 4),
+            (5, 3),
+            (7, 2),
+            (11, 2),
+        ] {
+            let pe = u64::pow(p, e);
+            for a in 0..pe {
+                assert_eq!(brute_force(a, pe), sqrt_mod_prime_power(a, p, e));
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_mod() {
+        let ps = PrimeSeq::new();
+        for n in 1..300 {
+            for a in 0..n {
+                assert_eq!(brute_force(a, n), ps.sqrt_mod(a, n));
+            }
+        }
+
+        assert_eq!(vec![1, 4, 11, 14], ps.sqrt_mod(1, 15));
+    }
+}
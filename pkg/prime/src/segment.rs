@@ -0,0 +1,182 @@
+use crate::PrimeSeq;
+use num_integer::Roots;
+use std::collections::VecDeque;
+
+const SEGMENT_SIZE: u64 = 1 << 15;
+
+/// An iterator which yields the primes within an interval, using a segmented sieve over just
+/// that interval. The iterator can be consumed from either end.
+pub struct PrimeRange {
+    ps: PrimeSeq,
+    /// The start of the interval which has not yet been sieved.
+    lo: u64,
+    /// The exclusive end of the interval which has not yet been sieved.
+    hi: u64,
+    /// Primes sieved from the low end of the interval, which have not yet been yielded.
+    front: VecDeque<u64>,
+    /// Primes sieved from the high end of the interval, which have not yet been yielded.
+    back: VecDeque<u64>,
+}
+
+impl PrimeRange {
+    pub(crate) fn new(ps: PrimeSeq, lo: u64, hi: u64) -> Self {
+        PrimeRange {
+            ps,
+            lo: lo.max(2),
+            hi,
+            front: VecDeque::new(),
+            back: VecDeque::new(),
+        }
+    }
+
+    /// Sieve the primes in `[lo, hi)`, using the base primes up to `√(hi - 1)`.
+    fn sieve(&self, lo: u64, hi: u64) -> VecDeque<u64> {
+        let mut is_prime = vec![true; (hi - lo) as usize];
+        let bound = (hi - 1).sqrt();
+
+        for p in self.ps.iter().take_while(|&p| p <= bound) {
+            let Some(start) = first_multiple(p, lo) else {
+                continue;
+            };
+            for m in (start..hi).step_by(p as usize) {
+                is_prime[(m - lo) as usize] = false;
+            }
+        }
+
+        is_prime
+            .into_iter()
+            .zip(lo..hi)
+            .filter_map(|(is_prime, n)| is_prime.then_some(n))
+            .collect()
+    }
+}
+
+/// Get the first multiple of `p` which is at least `lo` and is not `p` itself, or `None` if it
+/// does not fit in a `u64`, in which case the prime has no multiples left to cross off.
+#[inline]
+fn first_multiple(p: u64, lo: u64) -> Option<u64> {
+    Some((p * p).max(lo.div_ceil(p).checked_mul(p)?))
+}
+
+impl Iterator for PrimeRange {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some(p) = self.front.pop_front() {
+                return Some(p);
+            }
+
+            if self.lo >= self.hi {
+                return self.back.pop_front();
+            }
+
+            let end = self.hi.min(self.lo.saturating_add(SEGMENT_SIZE));
+            self.front = self.sieve(self.lo, end);
+            self.lo = end;
+        }
+    }
+}
+
+impl DoubleEndedIterator for PrimeRange {
+    fn next_back(&mut self) -> Option<u64> {
+        loop {
+            if let Some(p) = self.back.pop_back() {
+                return Some(p);
+            }
+
+            if self.lo >= self.hi {
+                return self.front.pop_back();
+            }
+
+            let start = self.lo.max(self.hi.saturating_sub(SEGMENT_SIZE));
+            self.back = self.sieve(start, self.hi);
+            self.hi = start;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::first_multiple;
+    use crate::PrimeSeq;
+
+    #[test]
+    fn range() {
+        let ps = PrimeSeq::new();
+        let expected = ps
+            .iter()
+            .skip_while(|&p| p < 1000)
+            .take_while(|&p| p < 200_000)
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, ps.range(1000..200_000).collect::<Vec<_>>());
+        assert_eq!(vec![2, 3, 5, 7], ps.range(0..10).collect::<Vec<_>>());
+        assert_eq!(vec![11, 13], ps.range(11..17).collect::<Vec<_>>());
+        assert_eq!(0, ps.range(24..29).count());
+    }
+
+    #[test]
+    fn first_multiples() {
+        assert_eq!(Some(9), first_multiple(3, 2));
+        assert_eq!(Some(12), first_multiple(3, 10));
+        assert_eq!(Some(12), first_multiple(3, 12));
+
+        //  Near the top of the type the next multiple can overflow, and there is nothing to sieve.
+        let p = 4_294_967_291;
+        assert_eq!(Some(u64::MAX / p * p), first_multiple(p, u64::MAX / p * p));
+        assert_eq!(None, first_multiple(p, u64::MAX - 2));
+        assert_eq!(Some(u64::MAX), first_multiple(3, u64::MAX - 1));
+    }
+
+    #[test]
+    fn range_rev() {
+        let ps = PrimeSeq::new();
+        let mut expected = ps.range(1000..200_000).collect::<Vec<_>>();
+        expected.reverse();
+
+        assert_eq!(expected, ps.range(1000..200_000).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn range_both_ends() {
+        let ps = PrimeSeq::new();
+        let expected = ps.range(0..100_000).collect::<Vec<_>>();
+        let mut range = ps.range(0..100_000);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        loop {
+            match (range.next(), range.next_back()) {
+                (Some(a), Some(b)) => {
+                    front.push(a);
+                    back.push(b);
+                }
+                (Some(a), None) => front.push(a),
+                (None, _) => break,
+            }
+        }
+
+        front.extend(back.into_iter().rev());
+        assert_eq!(expected, front);
+    }
+
+    #[test]
+    fn iter_from() {
+        let ps = PrimeSeq::new();
+        let mut iter = ps.iter_from(1_000_000_000);
+        assert_eq!(Some(1_000_000_007), iter.next());
+        assert_eq!(Some(1_000_000_009), iter.next());
+        assert_eq!(Some(1_000_000_021), iter.next());
+        assert_eq!(Some(2), ps.iter_from(0).next());
+    }
+
+    #[test]
+    fn rev_from() {
+        let ps = PrimeSeq::new();
+        assert_eq!(vec![7, 5, 3, 2], ps.rev_from(10).collect::<Vec<_>>());
+        assert_eq!(vec![7, 5, 3, 2], ps.rev_from(7).collect::<Vec<_>>());
+        assert_eq!(Some(999_999_937), ps.rev_from(999_999_999).next());
+        assert_eq!(0, ps.rev_from(1).count());
+    }
+}
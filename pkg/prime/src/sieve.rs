@@ -0,0 +1,127 @@
+use num_traits::{One, Zero};
+use std::ops::Mul;
+
+/// Tabulate a multiplicative function for every integer in `[0, limit]` using a linear sieve.
+///
+/// The function is defined by its values on prime powers, `f(p, e)` must return the value of
+/// the function at p<sup>e</sup>. The entry for `0` is zero, and the entry for `1` is one.
+pub fn multiplicative_sieve<T, F>(limit: usize, mut f: F) -> Vec<T>
+where
+    T: Zero + One + Mul<T, Output = T> + Clone,
+    F: FnMut(u64, u32) -> T,
+{
+    //  Each composite `m = i * p` is visited exactly once, by its smallest prime factor `p`.
+    //  Alongside the values, the sieve tracks the largest power of the smallest prime which
+    //  divides each number, `lpp(m)`, which allows the coprime split:
+    //      f(m) = f(m / lpp(m)) * f(lpp(m))
+    //  When `m` is itself a prime power the split is trivial, and `f(p, e)` is evaluated.
+    //
+    let len = limit + 1;
+    let mut values = vec![T::zero(); len];
+    let mut lpp = vec![0usize; len];
+    let mut exps = vec![0u32; len];
+    let mut primes = Vec::new();
+
+    if limit >= 1 {
+        values[1] = T::one();
+    }
+
+    for i in 2..len {
+        if lpp[i] == 0 {
+            primes.push(i);
+            lpp[i] = i;
+            exps[i] = 1;
+            values[i] = f(i as u64, 1);
+        }
+
+        for &p in &primes {
+            let m = i * p;
+            if m > limit {
+                break;
+            }
+
+            if i % p == 0 {
+                lpp[m] = lpp[i] * p;
+                exps[m] = exps[i] + 1;
+                values[m] = if lpp[m] == m {
+                    f(p as u64, exps[m])
+                } else {
+                    values[m / lpp[m]].clone() * values[lpp[m]].clone()
+                };
+                break;
+            }
+
+            lpp[m] = p;
+            exps[m] = 1;
+            values[m] = values[i].clone() * values[p].clone();
+        }
+    }
+
+    values
+}
+
+/// Tabulate Euler's totient φ(n) for every integer in `[0, limit]`.
+pub fn totient_sieve(limit: usize) -> Vec<u64> {
+    multiplicative_sieve(limit, |p, e| p.pow(e - 1) * (p - 1))
+}
+
+/// Tabulate the Möbius function μ(n) for every integer in `[0, limit]`.
+pub fn mobius_sieve(limit: usize) -> Vec<i32> {
+    multiplicative_sieve(limit, |_, e| if e == 1 { -1 } else { 0 })
+}
+
+/// Tabulate the number of divisors τ(n) for every integer in `[0, limit]`.
+pub fn divisor_count_sieve(limit: usize) -> Vec<u64> {
+    multiplicative_sieve(limit, |_, e| (e as u64) + 1)
+}
+
+/// Tabulate the sum of divisors σ(n) for every integer in `[0, limit]`.
+pub fn divisor_sum_sieve(limit: usize) -> Vec<u64> {
+    multiplicative_sieve(limit, |p, e| (p.pow(e + 1) - 1) / (p - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        divisor_count_sieve, divisor_sum_sieve, mobius_sieve, multiplicative_sieve,
+        totient_sieve,
+    };
+    use crate::{Factorize, PrimeSeq};
+
+    const LIMIT: usize = 2000;
+
+    #[test]
+    fn arithmetic_function_sieves() {
+        let ps = PrimeSeq::new();
+        let phi = totient_sieve(LIMIT);
+        let mu = mobius_sieve(LIMIT);
+        let tau = divisor_count_sieve(LIMIT);
+        let sigma = divisor_sum_sieve(LIMIT);
+
+        for n in 0..=LIMIT as u64 {
+            let i = n as usize;
+            assert_eq!(phi[i], n.totient(&ps));
+            assert_eq!(mu[i], n.mobius(&ps));
+            assert_eq!(tau[i], n.num_divisors(&ps));
+            assert_eq!(sigma[i], n.sum_divisors(&ps));
+        }
+    }
+
+    #[test]
+    fn custom_multiplicative_function() {
+        //  The number of square divisors of n
+        let squares = multiplicative_sieve::<u32, _>(100, |_, e| e / 2 + 1);
+        assert_eq!(squares[1], 1);
+        assert_eq!(squares[12], 2);   // {1, 4}
+        assert_eq!(squares[36], 4);   // {1, 4, 9, 36}
+        assert_eq!(squares[64], 4);   // {1, 4, 16, 64}
+        assert_eq!(squares[97], 1);   // {1}
+    }
+
+    #[test]
+    fn small_limits() {
+        assert_eq!(totient_sieve(0), vec![0]);
+        assert_eq!(totient_sieve(1), vec![0, 1]);
+        assert_eq!(totient_sieve(2), vec![0, 1, 1]);
+    }
+}
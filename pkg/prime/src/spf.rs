@@ -0,0 +1,262 @@
+use crate::Factor;
+use num_traits::{FromPrimitive, ToPrimitive};
+use std::{marker::PhantomData, ops::RangeInclusive};
+
+/// Unsigned integer types which can be used as the storage word of a sieve table.
+pub trait SieveWord: Copy + Default {
+    /// The largest value which can be stored in the word.
+    const MAX: u64;
+
+    /// Pack a value into the word, the value must not exceed `Self::MAX`.
+    fn pack(n: u64) -> Self;
+
+    /// Unpack the value stored in the word.
+    fn unpack(self) -> u64;
+}
+
+/// Implement the SieveWord trait for an unsigned integer type.
+macro_rules! sieve_word_trait_impl {
+    ($($t:ty)*) => ($(
+        impl SieveWord for $t {
+            const MAX: u64 = <$t>::MAX as u64;
+
+            #[inline]
+            fn pack(n: u64) -> Self {
+                n as $t
+            }
+
+            #[inline]
+            fn unpack(self) -> u64 {
+                self as u64
+            }
+        }
+    )*)
+}
+
+sieve_word_trait_impl!(u32 u64);
+
+/// A table of the smallest prime factor of every integer in `[0, limit]`, computed using a
+/// linear sieve. Numbers within the table can be factorized in `O(log n)`.
+///
+/// The storage word `S` determines the memory footprint of the table, `u32` entries halve the
+/// memory usage and are sufficient for any limit below 2<sup>32</sup>.
+pub struct SpfSieve<S = u64> {
+    spf: Vec<S>,
+    primes: Vec<S>,
+}
+
+/// A smallest prime factor table using `u32` entries.
+pub type CompactSpfSieve = SpfSieve<u32>;
+
+impl<S: SieveWord> SpfSieve<S> {
+    /// Sieve the smallest prime factor of every integer in `[0, limit]`.
+    pub fn new(limit: u64) -> Self {
+        assert!(limit <= S::MAX, "Sieve limit exceeds the capacity of the storage word");
+
+        //  In a linear sieve each composite is crossed out exactly once, by its smallest prime
+        //  factor. For each `i` the primes `p <= spf(i)` are visited, and `spf(i * p) = p`.
+        //  Entries for `0` and `1` are left as zero.
+        //
+        let len = (limit as usize) + 1;
+        let mut spf = vec![S::default(); len];
+        let mut primes = Vec::new();
+
+        for i in 2..len {
+            if spf[i].unpack() == 0 {
+                spf[i] = S::pack(i as u64);
+                primes.push(S::pack(i as u64));
+            }
+
+            let spf_i = spf[i].unpack();
+            for &p in &primes {
+                let p = p.unpack();
+                let m = (i as u64) * p;
+                if p > spf_i || m > limit {
+                    break;
+                }
+                spf[m as usize] = S::pack(p);
+            }
+        }
+
+        SpfSieve { spf, primes }
+    }
+
+    /// The largest number contained in the table.
+    #[inline]
+    pub fn limit(&self) -> u64 {
+        (self.spf.len() - 1) as u64
+    }
+
+    /// Get the smallest prime factor of a number, or `None` for `0` and `1`.
+    #[inline]
+    pub fn smallest_prime_factor(&self, n: u64) -> Option<u64> {
+        match self.entry(n) {
+            0 => None,
+            p => Some(p),
+        }
+    }
+
+    /// Evaluate if a number is prime.
+    #[inline]
+    pub fn contains(&self, n: u64) -> bool {
+        n > 1 && self.entry(n) == n
+    }
+
+    /// Get an iterator which yields all primes in the table in ascending order.
+    pub fn primes(&self) -> impl Iterator<Item = u64> + '_ {
+        self.primes.iter().map(|&p| p.unpack())
+    }
+
+    /// An iterator yielding all prime factors of the number in ascending order. Negative numbers
+    /// are factorized by their absolute value, consistent with `Factorize`.
+    pub fn factorize<T>(&self, n: T) -> SpfFactorsIter<'_, T, S>
+    where
+        T: FromPrimitive + ToPrimitive,
+    {
+        let n = n
+            .to_i128()
+            .and_then(|n| u64::try_from(n.unsigned_abs()).ok())
+            .expect("Number cannot be represented as a u64");
+
+        SpfFactorsIter {
+            num: n,
+            sieve: self,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get an iterator which yields each number in the range paired with its prime factors.
+    pub fn factorizations(&self, range: RangeInclusive<u64>) -> Factorizations<'_, S> {
+        Factorizations {
+            range,
+            sieve: self,
+        }
+    }
+
+    #[inline]
+    fn entry(&self, n: u64) -> u64 {
+        assert!(n <= self.limit(), "{} exceeds the sieve limit {}", n, self.limit());
+        self.spf[n as usize].unpack()
+    }
+}
+
+/// An iterator which yields the prime factors of a number using a smallest prime factor table.
+pub struct SpfFactorsIter<'a, T, S> {
+    num: u64,
+    sieve: &'a SpfSieve<S>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: FromPrimitive, S: SieveWord> Iterator for SpfFactorsIter<'_, T, S> {
+    type Item = Factor<T>;
+
+    fn next(&mut self) -> Option<Factor<T>> {
+        if self.num <= 1 {
+            return None;
+        }
+
+        let p = self.sieve.entry(self.num);
+        let mut exp = 0;
+        while self.num.is_multiple_of(p) {
+            exp += 1;
+            self.num /= p;
+        }
+
+        Some(Factor {
+            base: T::from_u64(p).unwrap(),
+            exp,
+        })
+    }
+}
+
+/// An iterator which yields each number in a range paired with an iterator over its factors.
+pub struct Factorizations<'a, S> {
+    range: RangeInclusive<u64>,
+    sieve: &'a SpfSieve<S>,
+}
+
+impl<'a, S: SieveWord> Iterator for Factorizations<'a, S> {
+    type Item = (u64, SpfFactorsIter<'a, u64, S>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range
+            .next()
+            .map(|n| (n, self.sieve.factorize(n)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<S: SieveWord> DoubleEndedIterator for Factorizations<'_, S> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range
+            .next_back()
+            .map(|n| (n, self.sieve.factorize(n)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompactSpfSieve, SpfSieve};
+    use crate::{Factor, Factorize, PrimeSeq};
+
+    #[test]
+    fn smallest_prime_factor() {
+        let sieve = SpfSieve::<u64>::new(100);
+        assert_eq!(sieve.smallest_prime_factor(0), None);
+        assert_eq!(sieve.smallest_prime_factor(1), None);
+        assert_eq!(sieve.smallest_prime_factor(2), Some(2));
+        assert_eq!(sieve.smallest_prime_factor(15), Some(3));
+        assert_eq!(sieve.smallest_prime_factor(49), Some(7));
+        assert_eq!(sieve.smallest_prime_factor(97), Some(97));
+        assert_eq!(sieve.smallest_prime_factor(100), Some(2));
+    }
+
+    #[test]
+    fn primes() {
+        let ps = PrimeSeq::new();
+        let sieve = CompactSpfSieve::new(10000);
+        let expected = ps
+            .iter()
+            .take_while(|&p| p <= 10000)
+            .collect::<Vec<_>>();
+
+        assert_eq!(sieve.primes().collect::<Vec<_>>(), expected);
+        for n in 0..=10000 {
+            assert_eq!(sieve.contains(n), ps.contains(n));
+        }
+    }
+
+    #[test]
+    fn factorize() {
+        let ps = PrimeSeq::new();
+        let sieve = SpfSieve::<u64>::new(5000);
+        for n in 0..=5000i64 {
+            let expected = n.factorize(&ps).collect::<Vec<_>>();
+            assert_eq!(sieve.factorize(n).collect::<Vec<_>>(), expected);
+            assert_eq!(sieve.factorize(-n).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn factorizations() {
+        let sieve = CompactSpfSieve::new(100);
+        let actual = sieve
+            .factorizations(10..=12)
+            .map(|(n, factors)| (n, factors.collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        let expected = vec![
+            (10, vec![Factor { base: 2, exp: 1 }, Factor { base: 5, exp: 1 }]),
+            (11, vec![Factor { base: 11, exp: 1 }]),
+            (12, vec![Factor { base: 2, exp: 2 }, Factor { base: 3, exp: 1 }]),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+}
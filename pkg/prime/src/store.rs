@@ -0,0 +1,101 @@
+/// Compact storage for an ascending list of primes.
+///
+/// Primes below 2<sup>32</sup> are stored as `u32`, which halves the memory usage of all but
+/// astronomically large caches. Primes at or above 2<sup>32</sup> are stored as `u64`.
+pub(crate) struct PrimeStore {
+    narrow: Vec<u32>,
+    wide: Vec<u64>,
+}
+
+impl PrimeStore {
+    #[inline]
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        PrimeStore {
+            narrow: Vec::with_capacity(capacity),
+            wide: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.narrow.len() + self.wide.len()
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, i: usize) -> u64 {
+        match self.narrow.get(i) {
+            Some(&p) => p as u64,
+            None => self.wide[i - self.narrow.len()],
+        }
+    }
+
+    #[inline]
+    pub(crate) fn last(&self) -> u64 {
+        match self.wide.last() {
+            Some(&p) => p,
+            None => *self.narrow.last().unwrap() as u64,
+        }
+    }
+
+    /// Append a prime, which must be greater than all stored primes.
+    #[inline]
+    pub(crate) fn push(&mut self, p: u64) {
+        match u32::try_from(p) {
+            Ok(p) if self.wide.is_empty() => self.narrow.push(p),
+            _ => self.wide.push(p),
+        }
+    }
+
+    /// Evaluate if the number is one of the stored primes.
+    #[inline]
+    pub(crate) fn contains(&self, n: u64) -> bool {
+        match u32::try_from(n) {
+            Ok(n) => self.narrow.binary_search(&n).is_ok(),
+            Err(_) => self.wide.binary_search(&n).is_ok(),
+        }
+    }
+
+    /// Get an iterator which yields the stored primes in ascending order.
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        self.narrow
+            .iter()
+            .map(|&p| p as u64)
+            .chain(self.wide.iter().copied())
+    }
+}
+
+impl Extend<u64> for PrimeStore {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        for p in iter {
+            self.push(p);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrimeStore;
+
+    #[test]
+    fn narrow_and_wide() {
+        let primes = [2, 3, 5, 4294967291, 4294967311, 4294967357];
+        let mut store = PrimeStore::with_capacity(4);
+        store.extend(primes);
+
+        assert_eq!(4, store.narrow.len());
+        assert_eq!(2, store.wide.len());
+        assert_eq!(primes.len(), store.len());
+        assert_eq!(4294967357, store.last());
+        assert_eq!(primes.to_vec(), store.iter().collect::<Vec<_>>());
+
+        for (i, &p) in primes.iter().enumerate() {
+            assert_eq!(p, store.get(i));
+            assert!(store.contains(p));
+        }
+
+        assert!(!store.contains(4));
+        assert!(!store.contains(4294967293));
+        assert!(!store.contains(4294967313));
+    }
+}
@@ -0,0 +1,224 @@
+use num_integer::Roots;
+use num_traits::FromPrimitive;
+use std::{mem, ops::Add};
+
+/// Compute the n<sup>th</sup> k-gonal number, ((k - 2)n<sup>2</sup> - (k - 4)n) / 2. For
+/// example k = 3 gives the triangular numbers and k = 5 the pentagonal numbers.
+pub fn polygonal(k: u64, n: u64) -> u64 {
+    assert!(k >= 3, "Polygons must have at least three sides");
+    ((k - 2) * n * n + 4 * n - k * n) / 2
+}
+
+/// Find the index n such that `x` is the n<sup>th</sup> k-gonal number, or `None` if `x` is not
+/// k-gonal. Zero has index zero.
+pub fn polygonal_index(k: u64, x: u64) -> Option<u64> {
+    assert!(k >= 3, "Polygons must have at least three sides");
+    if x == 0 {
+        return Some(0);
+    }
+
+    //  Solving the quadratic (k - 2)n^2 - (k - 4)n - 2x = 0 for positive n gives
+    //      n = ((k - 4) + √((k - 4)^2 + 8(k - 2)x)) / 2(k - 2)
+    //  so x is k-gonal if and only if the discriminant is a perfect square and the division is
+    //  exact.
+    //
+    let (a, b) = (k as i128 - 2, k as i128 - 4);
+    let disc = b * b + 8 * a * x as i128;
+    let root = disc.sqrt();
+    if root * root != disc || (b + root) % (2 * a) != 0 {
+        return None;
+    }
+    Some(((b + root) / (2 * a)) as u64)
+}
+
+/// Evaluate if `x` is a k-gonal number, in constant time.
+#[inline]
+pub fn is_polygonal(k: u64, x: u64) -> bool {
+    polygonal_index(k, x).is_some()
+}
+
+/// Compute the n<sup>th</sup> centered k-gonal number, kn(n - 1) / 2 + 1, which counts the dots in
+/// a central dot surrounded by n - 1 k-gonal layers.
+pub fn centered_polygonal(k: u64, n: u64) -> u64 {
+    assert!(k >= 3, "Polygons must have at least three sides");
+    assert!(n >= 1, "Centered polygonal numbers start at index one");
+    k * n * (n - 1) / 2 + 1
+}
+
+/// Find the index n such that `x` is the n<sup>th</sup> centered k-gonal number, or `None` if `x`
+/// is not centered k-gonal.
+pub fn centered_polygonal_index(k: u64, x: u64) -> Option<u64> {
+    assert!(k >= 3, "Polygons must have at least three sides");
+
+    //  Solving x - 1 = kn(n - 1) / 2 gives n(n - 1) = t where t = 2(x - 1) / k, and then
+    //  n = (1 + √(1 + 4t)) / 2.
+    //
+    let m = 2 * (x.checked_sub(1)? as u128);
+    if !m.is_multiple_of(k as u128) {
+        return None;
+    }
+    let disc = 1 + 4 * (m / k as u128);
+    let root = disc.sqrt();
+    if root * root != disc {
+        return None;
+    }
+    Some(root.div_ceil(2) as u64)
+}
+
+/// Evaluate if `x` is a centered k-gonal number, in constant time.
+#[inline]
+pub fn is_centered_polygonal(k: u64, x: u64) -> bool {
+    centered_polygonal_index(k, x).is_some()
+}
+
+/// Compute the n<sup>th</sup> k-gonal pyramidal number, the sum of the first n k-gonal numbers,
+/// n(n + 1)((k - 2)n - (k - 5)) / 6.
+pub fn pyramidal(k: u64, n: u64) -> u64 {
+    assert!(k >= 3, "Polygons must have at least three sides");
+    if n == 0 {
+        return 0;
+    }
+    n * (n + 1) * ((k - 2) * n + 5 - k) / 6
+}
+
+/// An iterator over the k-gonal or centered k-gonal numbers, starting at index one.
+///
+/// Consecutive figurate numbers have a constant second difference, so each step takes two
+/// additions.
+pub struct PolygonalIter<T> {
+    value: T,
+    diff: T,
+    step: T,
+}
+
+impl<T: FromPrimitive> PolygonalIter<T> {
+    /// Construct an iterator over the k-gonal numbers 1, k, 3k - 3, ...
+    pub fn new(k: u64) -> Self {
+        assert!(k >= 3, "Polygons must have at least three sides");
+        PolygonalIter {
+            value: T::from_u64(1).unwrap(),
+            diff: T::from_u64(k - 1).unwrap(),
+            step: T::from_u64(k - 2).unwrap(),
+        }
+    }
+
+    /// Construct an iterator over the centered k-gonal numbers 1, k + 1, 3k + 1, ...
+    pub fn centered(k: u64) -> Self {
+        assert!(k >= 3, "Polygons must have at least three sides");
+        PolygonalIter {
+            value: T::from_u64(1).unwrap(),
+            diff: T::from_u64(k).unwrap(),
+            step: T::from_u64(k).unwrap(),
+        }
+    }
+}
+
+impl<T> Iterator for PolygonalIter<T>
+where
+    T: Add<T, Output = T> + Clone,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.value.clone() + self.diff.clone();
+        self.diff = self.diff.clone() + self.step.clone();
+        Some(mem::replace(&mut self.value, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        centered_polygonal, centered_polygonal_index, is_centered_polygonal, is_polygonal,
+        polygonal, polygonal_index, pyramidal, PolygonalIter,
+    };
+
+    #[test]
+    fn polygonal_numbers() {
+        let sequences: &[(u64, [u64; 6])] = &[
+            (3, [1, 3, 6, 10, 15, 21]),
+            (4, [1, 4, 9, 16, 25, 36]),
+            (5, [1, 5, 12, 22, 35, 51]),
+            (6, [1, 6, 15, 28, 45, 66]),
+            (7, [1, 7, 18, 34, 55, 81]),
+            (8, [1, 8, 21, 40, 65, 96]),
+        ];
+
+        for &(k, expected) in sequences {
+            let iter = PolygonalIter::<u64>::new(k).take(6).collect::<Vec<_>>();
+            assert_eq!(expected.to_vec(), iter);
+            for (i, &x) in expected.iter().enumerate() {
+                assert_eq!(x, polygonal(k, i as u64 + 1));
+                assert_eq!(Some(i as u64 + 1), polygonal_index(k, x));
+            }
+            assert_eq!(0, polygonal(k, 0));
+            assert_eq!(Some(0), polygonal_index(k, 0));
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        for k in 3..=12 {
+            let values = PolygonalIter::<u64>::new(k)
+                .take_while(|&x| x <= 100_000)
+                .collect::<Vec<_>>();
+            for x in 1..=100_000 {
+                assert_eq!(values.binary_search(&x).is_ok(), is_polygonal(k, x));
+            }
+        }
+
+        //  T(285) = P(165) = H(143) = 40755
+        assert_eq!(Some(285), polygonal_index(3, 40755));
+        assert_eq!(Some(165), polygonal_index(5, 40755));
+        assert_eq!(Some(143), polygonal_index(6, 40755));
+        assert!(is_polygonal(5, polygonal(5, 1_000_000)));
+        assert!(!is_polygonal(5, polygonal(5, 1_000_000) + 1));
+    }
+
+    #[test]
+    fn centered() {
+        assert_eq!(
+            vec![1, 7, 19, 37, 61, 91],
+            PolygonalIter::<u64>::centered(6)
+                .take(6)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 5, 13, 25, 41, 61],
+            PolygonalIter::<u64>::centered(4)
+                .take(6)
+                .collect::<Vec<_>>()
+        );
+
+        for k in 3..=8 {
+            let values = PolygonalIter::<u64>::centered(k)
+                .take_while(|&x| x <= 10_000)
+                .collect::<Vec<_>>();
+            for (i, &x) in values.iter().enumerate() {
+                assert_eq!(x, centered_polygonal(k, i as u64 + 1));
+                assert_eq!(Some(i as u64 + 1), centered_polygonal_index(k, x));
+            }
+            for x in 0..=10_000 {
+                assert_eq!(values.contains(&x), is_centered_polygonal(k, x));
+            }
+        }
+    }
+
+    #[test]
+    fn pyramidal_numbers() {
+        assert_eq!(
+            vec![1, 4, 10, 20, 35],
+            (1..=5).map(|n| pyramidal(3, n)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![1, 5, 14, 30, 55],
+            (1..=5).map(|n| pyramidal(4, n)).collect::<Vec<_>>()
+        );
+        for k in 3..=8 {
+            let sum = PolygonalIter::<u64>::new(k).take(20).sum::<u64>();
+            assert_eq!(sum, pyramidal(k, 20));
+            assert_eq!(0, pyramidal(k, 0));
+        }
+    }
+}
@@ -1,6 +1,13 @@
 use num_traits::{One, Zero};
 use std::{mem, ops::Add};
 
+mod figurate;
+
+pub use figurate::{
+    centered_polygonal, centered_polygonal_index, is_centered_polygonal, is_polygonal, polygonal,
+    polygonal_index, pyramidal, PolygonalIter,
+};
+
 /// Fibonacci sequence iterator.
 pub struct FibonacciIter<T> {
     m: T,
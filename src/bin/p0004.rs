@@ -1,4 +1,4 @@
-use integer::Integer;
+use integer::PalindromeIter;
 
 const RADIX: usize = 10;
 
@@ -10,21 +10,20 @@ fn largest_palindrome_product(d: u32) -> usize {
     let min = RADIX.pow(d - 1);
     let max = RADIX.pow(d) - 1;
 
-    let mut p: usize;
-    let mut n: usize = 0;
-
-    for a in (min..=max).rev() {
-        for b in (a..=max).rev() {
-            p = a * b;
-            if p <= n {
-                break;
-            }
-            if p.is_palindromic(RADIX) {
-                n = std::cmp::max(n, p);
-            }
-        }
-    }
-    n
+    //  NOTE: Palindromes are generated from largest to smallest, so the first with a factor pair
+    //  in range is the answer. For each palindrome it suffices to check factors a >= √p, since
+    //  the cofactor p / a is then at most a.
+    //
+    (1..=2 * d)
+        .rev()
+        .flat_map(|len| PalindromeIter::with_digits(len, RADIX).rev())
+        .find(|&p| {
+            (min..=max)
+                .rev()
+                .take_while(|&a| a * a >= p)
+                .any(|a| p % a == 0 && p / a >= min)
+        })
+        .unwrap()
 }
 
 euler::register_problem!("Largest Palindrome Product", solve, "906609");
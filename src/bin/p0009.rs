@@ -1,19 +1,15 @@
-use num_integer::Roots;
-use std::{cmp, iter};
+use integer::Integer;
 
 fn solve() -> String {
     find_triplet_product(1000).to_string()
 }
 
 fn find_triplet_product(sum: u64) -> u64 {
-    (3..sum - 1)
-        .flat_map(|c| {
-            let a_max = cmp::min((sum - c) / 2, (c * c / 2).sqrt());
-            (1..a_max).zip(iter::repeat(c))
-        })
-        .map(|(a, c)| (a, sum - a - c, c))
-        .find(|&(a, b, c)| a * a + b * b == c * c)
-        .map(|(a, b, c)| a * b * c)
+    (1..sum / 3)
+        .flat_map(|a| (a + 1..sum / 2).map(move |b| (a, b)))
+        .map(|(a, b)| (a, b, a * a + b * b))
+        .find(|&(a, b, c2)| c2.is_perfect_square() && a + b + c2.integer_sqrt() == sum)
+        .map(|(a, b, c2)| a * b * c2.integer_sqrt())
         .unwrap()
 }
 
@@ -1,42 +1,22 @@
-use std::collections::VecDeque;
+use integer::Integer;
+use num_bigint::BigUint;
 
 fn solve() -> String {
     compute_sum(INPUT)
 }
 
 fn compute_sum(input: &str) -> String {
-    let numbers: Vec<Vec<u64>> = input
+    let radix = BigUint::from(10u32);
+    let sum = input
         .trim()
         .lines()
-        .map(parse_rev_digits)
-        .collect();
+        .map(|line| {
+            BigUint::from_rev_digits(line.bytes().map(|b| BigUint::from(b - b'0')), radix.clone())
+        })
+        .sum::<BigUint>();
 
-    let mut digits = VecDeque::with_capacity(10);
-    let mut carry = 0u64;
-
-    for d in 0..numbers[0].len() {
-        for i in 0..numbers.len() {
-            carry += numbers[i][d]
-        }
-
-        digits.push_front(carry % 10);
-        carry /= 10;
-    }
-
-    digits
-        .iter()
-        .take(10)
-        .fold(carry, |acc, d| acc * 10 + d)
-        .to_string()[0..10]
-        .to_string()
-}
-
-#[inline]
-fn parse_rev_digits(line: &str) -> Vec<u64> {
-    line.chars()
-        .map(|ch| ch.to_digit(10).unwrap() as u64)
-        .rev()
-        .collect()
+    let leading = sum.to_digits(radix.clone()).rev().take(10);
+    BigUint::from_rev_digits(leading, radix).to_string()
 }
 
 euler::register_problem!("Large Sum", solve, "5537376230");
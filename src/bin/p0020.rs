@@ -1,17 +1,12 @@
+use integer::Integer;
 use num_bigint::BigUint;
 
 fn solve() -> String {
     factorial_digit_sum(100).to_string()
 }
 
-fn factorial_digit_sum(x: u32) -> u32 {
-    (1..=x)
-        .map(BigUint::from)
-        .fold(num_traits::one::<BigUint>(), |acc, n| acc * n)
-        .to_string()
-        .chars()
-        .filter_map(|c| c.to_digit(10))
-        .sum()
+fn factorial_digit_sum(x: u32) -> BigUint {
+    BigUint::from(x).factorial().digit_sum(BigUint::from(10u32))
 }
 
 euler::register_problem!("Factorial Digit Sum", solve, "648");
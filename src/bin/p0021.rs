@@ -1,13 +1,14 @@
-﻿use prime::{Factorize, PrimeSeq};
+﻿use prime::divisor_sum_sieve;
 
 fn solve() -> String {
     sum_divisors(10000).to_string()
 }
 
 fn sum_divisors(n: usize) -> usize {
-    let primes_seq = PrimeSeq::new();
-    let sum_of_divs = (0..=n)
-        .map(|n| n.sum_proper_divisors(&primes_seq))
+    let sum_of_divs = divisor_sum_sieve(n)
+        .into_iter()
+        .enumerate()
+        .map(|(n, sum)| sum as usize - n)
         .collect::<Vec<_>>();
 
     sum_of_divs
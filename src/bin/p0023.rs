@@ -1,11 +1,11 @@
-﻿use prime::{Factorize, PrimeSeq};
+﻿use prime::divisor_sum_sieve;
 
 const LIMIT: usize = 28123;
 
 fn solve() -> String {
-    let primes = PrimeSeq::new();
+    let sum_of_divs = divisor_sum_sieve(LIMIT);
     let abundant = (12..=LIMIT)
-        .filter(|&n| n.sum_proper_divisors(&primes) > n)
+        .filter(|&n| sum_of_divs[n] as usize - n > n)
         .collect::<Vec<_>>();
 
     let mut sum_of_abundant_composites = 0;
@@ -1,3 +1,4 @@
+use integer::Integer;
 use num_bigint::BigUint;
 use num_traits::one;
 use seq::FibonacciIter;
@@ -6,10 +7,10 @@ fn solve() -> String {
     n_digit_fibonacci(1000).to_string()
 }
 
-fn n_digit_fibonacci(d: u32) -> usize {
-    let max = BigUint::from(10u32).pow(d - 1) - one::<BigUint>();
+fn n_digit_fibonacci(d: usize) -> usize {
+    let radix = BigUint::from(10u32);
     FibonacciIter::<BigUint>::start_at(one::<BigUint>(), one::<BigUint>())
-        .take_while(|n| *n <= max)
+        .take_while(|n| n.num_digits(radix.clone()) < d)
         .count()
         + 1
 }
@@ -1,31 +1,11 @@
-﻿use std::collections::HashMap;
+﻿use prime::PrimeSeq;
 
 fn solve() -> String {
+    let ps = PrimeSeq::new();
     (2..1000)
-        .max_by_key(|&n| get_reciprocal_cycle(n))
+        .max_by_key(|&n| ps.radix_expansion(1, n, 10).period_len())
         .unwrap()
         .to_string()
 }
 
-fn get_reciprocal_cycle(n: usize) -> usize {
-    let mut seen: HashMap<usize, usize> = HashMap::new();
-    let mut rem = 1;
-    let mut pos = 0;
-
-    loop {
-        rem = rem % n;
-
-        if rem == 0 {
-            return 0;
-        }
-        if let Some(&prev) = seen.get(&rem) {
-            return pos - prev;
-        }
-
-        seen.insert(rem, pos);
-        rem *= 10;
-        pos += 1;
-    }
-}
-
-euler::register_problem!("Reciprocal Cycles", solve, "983");
\ No newline at end of file
+euler::register_problem!("Reciprocal Cycles", solve, "983");
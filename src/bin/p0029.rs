@@ -1,25 +1,23 @@
-﻿use std::collections::HashSet;
-use prime::{PrimeSeq, Factorize, Factor};
+﻿use integer::Integer;
+use std::collections::HashSet;
 
 fn solve() -> String {
     count_distinct_powers(2, 100).to_string()
 }
 
-fn count_distinct_powers(min: i32, max: i32) -> usize {
+fn count_distinct_powers(min: u32, max: u32) -> usize {
+    //  NOTE: Writing each base as a perfect power c^k with the smallest possible c gives a
+    //  canonical form for every term, a^b = c^(k * b).
+    //
     let mut set = HashSet::new();
-    let ps = PrimeSeq::new();
-
     for a in min..=max {
-        let a_factors = a.factorize(&ps).collect::<Vec<_>>();
+        let (c, k) = a.is_perfect_power().unwrap_or((a, 1));
         for b in min..=max {
-            set.insert(a_factors
-                .iter()
-                .map(|a_factor| Factor {base: a_factor.base, exp: a_factor.exp * b})
-                .collect::<Vec<_>>());
+            set.insert((c, k * b));
         }
     }
 
     set.len()
 }
 
-euler::register_problem!("Distinct Powers", solve, "9183");
\ No newline at end of file
+euler::register_problem!("Distinct Powers", solve, "9183");
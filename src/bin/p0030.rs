@@ -16,8 +16,10 @@ fn solve() -> String {
 
 fn check(n: usize) -> bool {
     n == n
-        .to_digits(10)
-        .map(|d| d.pow(5))
+        .digit_histogram::<10>()
+        .iter()
+        .enumerate()
+        .map(|(d, &count)| count as usize * d.pow(5))
         .sum()
 }
 
@@ -1,37 +1,26 @@
-﻿use integer::Integer;
-use iter::PermutationIter;
+﻿use integer::is_pandigital_concat;
 use std::collections::HashSet;
 
-const DIGITS: usize = 9;
 const RADIX: usize = 10;
 
 fn solve() -> String {
-    let digits = (1..=DIGITS).collect::<Vec<usize>>();
-    let mut perm = PermutationIter::new(&digits, DIGITS);
-    let mut prod = HashSet::new();
-
-    while let Some((p, _)) = perm.next() {
-        check(p, &mut prod);
+    //  NOTE: The multiplicand, multiplier and product use nine digits in total, which is only
+    //  possible when the product has four digits, so the multiplicand has at most two digits
+    //  and the multiplier at most four.
+    //
+    let mut products = HashSet::new();
+    for m in 1..100 {
+        for n in (m + 1..10_000).take_while(|&n| m * n < 10_000) {
+            if is_pandigital_concat(&[m, n, m * n], RADIX, 1..=9) {
+                products.insert(m * n);
+            }
+        }
     }
 
-    prod
+    products
         .iter()
         .sum::<usize>()
         .to_string()
 }
 
-fn check(digits: Vec<usize>, products: &mut HashSet<usize>) {
-    for i in 1..=(DIGITS / 2) {
-        for j in (i + 1)..=(i + 2) {
-            let m = usize::from_rev_digits(digits.iter().take(i).copied(), RADIX);
-            let n = usize::from_rev_digits(digits.iter().skip(i).take(j - i).copied(), RADIX);
-            let p = usize::from_rev_digits(digits.iter().skip(j).copied(), RADIX);
-
-            if m * n == p {
-                products.insert(p);
-            }
-        }
-    }
-}
-
-euler::register_problem!("Pandigital Products", solve, "45228");
\ No newline at end of file
+euler::register_problem!("Pandigital Products", solve, "45228");
@@ -1,7 +1,7 @@
-﻿use num_integer::Integer;
+﻿use integer::Ratio;
 
 fn solve() -> String {
-    let (pn, pd) =(10..100)
+    let product = (10..100)
         .flat_map(|n| (n + 1..=100).map(move |d| (n, d)))
         .filter(|&(n, d)| n % 10 != 0 || d % 10 != 0)
         .filter(|&(n, d)| {
@@ -9,12 +9,10 @@ fn solve() -> String {
             let (d1, d2) = (d / 10, d % 10);
             (n1 == d2 && n2 * d == n * d1) || (n2 == d1 && n1 * d == n * d2)
         })
-        .fold((1, 1), |(np, nd), (n, d)| (n * np, d * nd));
+        .map(|(n, d)| Ratio::new(n, d))
+        .product::<Ratio<u32>>();
 
-    let gcd = pn.gcd(&pd);
-    let den = pd / gcd;
-
-    den.to_string()
+    product.denom().to_string()
 }
 
 euler::register_problem!("Digit Cancelling Fractions", solve, "100");
\ No newline at end of file
@@ -15,8 +15,10 @@ fn solve() -> String {
     //
     (10..50_000)
         .filter(|&n| {
-            n == n.to_digits(10)
-                .map(|d| fs[d])
+            n == n.digit_histogram::<10>()
+                .iter()
+                .zip(&fs)
+                .map(|(&count, f)| count as usize * f)
                 .sum()
         })
         .sum::<usize>()
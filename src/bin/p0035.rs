@@ -13,16 +13,8 @@ fn solve() -> String {
 
 fn is_circular(ps: &PrimeSeq, p: u64) -> bool {
     const RADIX: u64 = 10;
-    let digits = p.into_digits(RADIX).collect::<Vec<_>>();
-    let length = digits.len();
-
-    (1..length)
-        .map(|i| u64::from_digits(digits
-            .iter()
-            .copied()
-            .cycle()
-            .skip(i)
-            .take(length), RADIX))
+    p.rotations(RADIX)
+        .skip(1)
         .all(|r| ps.contains(r))
 }
 
@@ -1,10 +1,9 @@
-﻿use integer::Integer;
+﻿use integer::double_base_palindromes;
 
 fn solve() -> String {
-    (1..1_000_000)
-        .filter(|&n| n.is_palindromic(10) && n.is_palindromic(2))
+    double_base_palindromes(1..1_000_000, 10, 2)
         .sum::<usize>()
         .to_string()
 }
 
-euler::register_problem!("Double-base Palindromes", solve, "872187");
\ No newline at end of file
+euler::register_problem!("Double-base Palindromes", solve, "872187");
@@ -4,8 +4,7 @@ use prime::PrimeSeq;
 fn solve() -> String {
     let primes = PrimeSeq::new();
     primes
-        .iter()
-        .skip_while(|&n| n <= 7)
+        .iter_from(11)
         .filter(|&n| is_truncatable(&primes, n))
         .take(11)
         .sum::<u64>()
@@ -14,16 +13,10 @@ fn solve() -> String {
 
 fn is_truncatable(ps: &PrimeSeq, n: u64) -> bool {
     const RADIX: u64 = 10;
-    let digits = n.into_digits(RADIX).collect::<Vec<_>>();
-    let length = digits.len();
-
-    (1..length)
-        .map(|i| {
-            let a = u64::from_digits(digits.iter().copied().skip(i), RADIX);
-            let b = u64::from_digits(digits.iter().copied().take(length - i), RADIX);
-            (a, b)
-        })
-        .all(|(a, b)| ps.contains(a) && ps.contains(b))
+    n.left_truncations(RADIX)
+        .skip(1)
+        .chain(n.right_truncations(RADIX).skip(1))
+        .all(|t| ps.contains(t))
 }
 
 euler::register_problem!("Truncatable Primes", solve, "748317");
\ No newline at end of file
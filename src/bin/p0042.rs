@@ -1,5 +1,4 @@
-﻿use std::collections::HashSet;
-use seq::TriangularIter;
+﻿use seq::is_polygonal;
 
 fn solve(input: &str) -> String {
     let words = input
@@ -12,14 +11,9 @@ fn solve(input: &str) -> String {
             .sum::<usize>())
         .collect::<Vec<_>>();
 
-    let max = words.iter().max().unwrap();
-    let tri = TriangularIter::new()
-        .take_while(|&t| t <= *max)
-        .collect::<HashSet<usize>>();
-
     words
         .iter()
-        .filter(|&word| tri.contains(word))
+        .filter(|&&word| is_polygonal(3, word as u64))
         .count()
         .to_string()
 }
@@ -5,7 +5,7 @@ fn solve() -> String {
     (1..)
         .find(|&n| {
             (n..n + 4)
-                .map(|n| n.factorize(&ps).count())
+                .map(|n| n.omega(&ps))
                 .all(|num_factors| num_factors == 4)
         })
         .unwrap()
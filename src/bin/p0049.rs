@@ -3,17 +3,14 @@ use iter::CombinationIter;
 use prime::PrimeSeq;
 use std::collections::HashMap;
 
-const PRIMES: [u64; 10] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29];
 const RADIX: u64 = 10;
 const KNOWN: u64 = 1487;
 
 fn solve() -> String {
     let prime_sets: Vec<Vec<u64>>  = PrimeSeq::new()
-        .iter()
-        .skip_while(|&p| p < 01_000)
-        .take_while(|&p| p < 10_000)
-        .fold(HashMap::<u64, Vec<u64>>::new(), |mut map, p| {
-            map.entry(hash(p)).or_default().push(p);
+        .range(1_000..10_000)
+        .fold(HashMap::<u128, Vec<u64>>::new(), |mut map, p| {
+            map.entry(p.digit_signature(RADIX)).or_default().push(p);
             map
         })
         .into_values()
@@ -37,11 +34,4 @@ fn solve() -> String {
     unreachable!("No solution found");
 }
 
-fn hash(prime: u64) -> u64 {
-    prime
-        .into_digits(RADIX)
-        .map(|d| PRIMES[d as usize])
-        .product()
-}
-
 euler::register_problem!("Prime Permutations", solve, "296962999629");
\ No newline at end of file
@@ -9,8 +9,7 @@ fn solve() -> String {
     let primes = PrimeSeq::new();
     let mut family = Vec::with_capacity(COUNT);
     primes
-        .iter()
-        .skip_while(|&p| p < RADIX)
+        .iter_from(RADIX)
         .filter_map(|p| find_family(&primes, p, &mut family))
         .next()
         .unwrap()
@@ -1,5 +1,4 @@
 ﻿use integer::Integer;
-use std::collections::HashSet;
 
 const RADIX: usize = 10;
 const MULTIPLES: &[usize] = &[2, 3, 4, 5, 6];
@@ -12,18 +11,10 @@ fn solve() -> String {
 }
 
 fn check(n: usize) -> bool {
-    let a: Vec<_> = (n * MULTIPLES[0]).into_digits(RADIX).collect();
-    (1..MULTIPLES.len())
-        .all(|i| {
-            let b: Vec<_> = (n * MULTIPLES[i]).into_digits(RADIX).collect();
-            same_elements(&a, &b)
-        })
-}
-
-fn same_elements(a: &[usize], b: &[usize]) -> bool {
-    let set_a: HashSet<_> = a.iter().collect();
-    let set_b: HashSet<_> = b.iter().collect();
-    set_a == set_b
+    let signature = (n * MULTIPLES[0]).digit_signature(RADIX);
+    MULTIPLES[1..]
+        .iter()
+        .all(|&m| (n * m).digit_signature(RADIX) == signature)
 }
 
 euler::register_problem!("Permuted Multiples", solve, "142857");
\ No newline at end of file